@@ -0,0 +1,265 @@
+use crate::alloc::allocations;
+use common::{Json, ParseError, Solution};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+// a median more than this many percent slower than the baseline counts as regression
+pub const REGRESSION_THRESHOLD: f64 = 10.0;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Measurement {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    // medians per run
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DayBenchmark {
+    pub day: u8,
+    // in the order of `PHASES`
+    pub phases: [Measurement; 3],
+}
+
+pub type Bench = fn(&str, usize) -> Result<DayBenchmark, ParseError>;
+
+pub fn run<S: Solution>(content: &str, runs: usize) -> Result<DayBenchmark, ParseError> {
+    // parse once up front, to report errors and to have an input for the parts
+    let input = common::parse::<S>(content)?;
+    let parse = measure(runs, || common::parse::<S>(content));
+    let part1 = measure(runs, || S::part1(&input));
+    let part2 = measure(runs, || S::part2(&input));
+    Ok(DayBenchmark {
+        day: S::DAY,
+        phases: [parse, part1, part2],
+    })
+}
+
+pub fn measure<T>(runs: usize, mut f: impl FnMut() -> T) -> Measurement {
+    let runs = runs.max(1);
+    let mut times: Vec<Duration> = Vec::with_capacity(runs);
+    let mut counts: Vec<u64> = Vec::with_capacity(runs);
+    let mut bytes: Vec<u64> = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let before = allocations();
+        let start = Instant::now();
+        let result = black_box(f());
+        let elapsed = start.elapsed();
+        let after = allocations();
+        // freeing the result is not part of the measurement
+        drop(result);
+        times.push(elapsed);
+        counts.push(after.count - before.count);
+        bytes.push(after.bytes - before.bytes);
+    }
+    times.sort_unstable();
+    counts.sort_unstable();
+    bytes.sort_unstable();
+    Measurement {
+        min: times[0],
+        median: times[runs / 2],
+        max: times[runs - 1],
+        allocations: counts[runs / 2],
+        allocated_bytes: bytes[runs / 2],
+    }
+}
+
+pub fn to_json(runs: usize, results: &[DayBenchmark]) -> Json {
+    let days = results
+        .iter()
+        .map(|result| {
+            let phases = PHASES.iter().zip(&result.phases).map(|(phase, m)| {
+                let measurement = Json::object([
+                    ("min_ns", Json::from(m.min.as_nanos())),
+                    ("median_ns", Json::from(m.median.as_nanos())),
+                    ("max_ns", Json::from(m.max.as_nanos())),
+                    ("allocations", Json::from(m.allocations)),
+                    ("allocated_bytes", Json::from(m.allocated_bytes)),
+                ]);
+                (*phase, measurement)
+            });
+            Json::object([("day", Json::from(result.day))].into_iter().chain(phases))
+        })
+        .collect();
+    Json::object([("runs", Json::from(runs)), ("days", Json::Array(days))])
+}
+
+pub fn format_table(results: &[DayBenchmark]) -> String {
+    let mut out = String::with_capacity(128 * results.len());
+    let _ = writeln!(
+        out,
+        "Day  Phase  {:>12} {:>12} {:>12} {:>10} {:>12}",
+        "min", "median", "max", "allocs", "bytes"
+    );
+    for result in results {
+        for (phase, m) in PHASES.iter().zip(&result.phases) {
+            let _ = writeln!(
+                out,
+                "{:02}   {:<5}  {:>12} {:>12} {:>12} {:>10} {:>12}",
+                result.day,
+                phase,
+                format!("{:.1?}", m.min),
+                format!("{:.1?}", m.median),
+                format!("{:.1?}", m.max),
+                m.allocations,
+                m.allocated_bytes
+            );
+        }
+    }
+    out
+}
+
+// median time per day and phase of a previous run, stored with `to_json`
+pub type Baseline = HashMap<(u8, String), Duration>;
+
+pub fn parse_baseline(content: &str) -> Result<Baseline, String> {
+    let json = Json::parse(content).map_err(|e| e.locate(content).to_string())?;
+    let days = json
+        .get("days")
+        .and_then(|days| days.as_array())
+        .ok_or_else(|| "expected a list of 'days' in the baseline".to_owned())?;
+    let mut baseline: Baseline = HashMap::with_capacity(days.len() * PHASES.len());
+    for day in days {
+        let number = day
+            .get("day")
+            .and_then(|d| d.as_i128())
+            .and_then(|d| u8::try_from(d).ok())
+            .ok_or_else(|| format!("expected a day number in baseline entry {day}"))?;
+        for phase in PHASES {
+            if let Some(median) = day
+                .get(phase)
+                .and_then(|p| p.get("median_ns"))
+                .and_then(|m| m.as_i128())
+            {
+                let median = Duration::from_nanos(median.try_into().unwrap_or(u64::MAX));
+                baseline.insert((number, phase.to_owned()), median);
+            }
+        }
+    }
+    Ok(baseline)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Comparison {
+    pub day: u8,
+    pub phase: &'static str,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    // in percent, positive if slower
+    pub fn change(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64();
+        if baseline == 0.0 {
+            return 0.0;
+        }
+        (self.current.as_secs_f64() - baseline) / baseline * 100.0
+    }
+
+    pub fn is_regression(&self) -> bool {
+        self.change() > REGRESSION_THRESHOLD
+    }
+}
+
+pub fn compare(baseline: &Baseline, results: &[DayBenchmark]) -> Vec<Comparison> {
+    results
+        .iter()
+        .flat_map(|result| {
+            PHASES.iter().zip(&result.phases).filter_map(|(phase, m)| {
+                let baseline = baseline.get(&(result.day, phase.to_string()))?;
+                Some(Comparison {
+                    day: result.day,
+                    phase,
+                    baseline: *baseline,
+                    current: m.median,
+                })
+            })
+        })
+        .collect()
+}
+
+pub fn format_comparison(comparisons: &[Comparison]) -> String {
+    let mut out = String::with_capacity(80 * comparisons.len());
+    let _ = writeln!(
+        out,
+        "Day  Phase  {:>12} {:>12} {:>9}",
+        "baseline", "median", "change"
+    );
+    for c in comparisons {
+        let _ = writeln!(
+            out,
+            "{:02}   {:<5}  {:>12} {:>12} {:>+8.1}%{}",
+            c.day,
+            c.phase,
+            format!("{:.1?}", c.baseline),
+            format!("{:.1?}", c.current),
+            c.change(),
+            if c.is_regression() {
+                "  regression"
+            } else {
+                ""
+            }
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn measurement(median_ms: u64) -> Measurement {
+        Measurement {
+            min: Duration::from_millis(median_ms),
+            median: Duration::from_millis(median_ms),
+            max: Duration::from_millis(median_ms),
+            allocations: 3,
+            allocated_bytes: 100,
+        }
+    }
+
+    #[test]
+    fn measure_orders_the_runs() {
+        // when
+        let m = measure(5, || vec![0u8; 64]);
+
+        // then
+        assert!(m.min <= m.median && m.median <= m.max);
+        assert!(m.allocations >= 1);
+        assert!(m.allocated_bytes >= 64);
+    }
+
+    #[test]
+    fn compare_finds_regressions_against_json_baseline() {
+        // given
+        let old = [DayBenchmark {
+            day: 6,
+            phases: [measurement(1), measurement(100), measurement(1000)],
+        }];
+        let new = [DayBenchmark {
+            day: 6,
+            phases: [measurement(1), measurement(105), measurement(2000)],
+        }];
+        let baseline =
+            parse_baseline(&to_json(3, &old).to_string()).expect("expected baseline to parse");
+
+        // when
+        let comparisons = compare(&baseline, &new);
+
+        // then
+        let regressions: Vec<&str> = comparisons
+            .iter()
+            .filter(|c| c.is_regression())
+            .map(|c| c.phase)
+            .collect();
+        assert_eq!(comparisons.len(), 3);
+        assert_eq!(regressions, vec!["part2"]);
+    }
+}
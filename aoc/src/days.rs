@@ -0,0 +1,74 @@
+use crate::bench::{self, Bench};
+use crate::verify::{self, Verifier};
+use common::random::{GenOptions, Rng};
+use common::{solve, Answers, ParseError, Solution};
+
+// Every day implements the same `Solution` trait, so the runner does not need to know anything
+// about the individual puzzles.
+pub type Solver = fn(&str) -> Result<Answers, ParseError>;
+
+pub type Generator = fn(&mut Rng, &GenOptions) -> Option<String>;
+
+pub struct Day {
+    pub solve: Solver,
+    pub bench: Bench,
+    pub verify: Verifier,
+    pub generate: Generator,
+}
+
+const fn day<S: Solution>() -> Day {
+    Day {
+        solve: solve::<S>,
+        bench: bench::run::<S>,
+        verify: verify::solve_timed::<S>,
+        generate: S::generate,
+    }
+}
+
+pub static DAYS: [Day; 25] = [
+    day::<day_01::Day01>(),
+    day::<day_02::Day02>(),
+    day::<day_03::Day03>(),
+    day::<day_04::Day04>(),
+    day::<day_05::Day05>(),
+    day::<day_06::Day06>(),
+    day::<day_07::Day07>(),
+    day::<day_08::Day08>(),
+    day::<day_09::Day09>(),
+    day::<day_10::Day10>(),
+    day::<day_11::Day11>(),
+    day::<day_12::Day12>(),
+    day::<day_13::Day13>(),
+    day::<day_14::Day14>(),
+    day::<day_15::Day15>(),
+    day::<day_16::Day16>(),
+    day::<day_17::Day17>(),
+    day::<day_18::Day18>(),
+    day::<day_19::Day19>(),
+    day::<day_20::Day20>(),
+    day::<day_21::Day21>(),
+    day::<day_22::Day22>(),
+    day::<day_23::Day23>(),
+    day::<day_24::Day24>(),
+    day::<day_25::Day25>(),
+];
+
+fn get(day: u8) -> Option<&'static Day> {
+    DAYS.get((day as usize).checked_sub(1)?)
+}
+
+pub fn solver(day: u8) -> Option<Solver> {
+    get(day).map(|d| d.solve)
+}
+
+pub fn benchmark(day: u8) -> Option<Bench> {
+    get(day).map(|d| d.bench)
+}
+
+pub fn verifier(day: u8) -> Option<Verifier> {
+    get(day).map(|d| d.verify)
+}
+
+pub fn generator(day: u8) -> Option<Generator> {
+    get(day).map(|d| d.generate)
+}
@@ -0,0 +1,365 @@
+use crate::days::Solver;
+use common::random::Rng;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::panic::{self, PanicHookInfo};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+// Minimized crashing inputs, named day-NN-<hash>.txt. The tests below replay all of them.
+pub const REGRESSIONS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/regressions");
+
+// the longest input the mutations produce, larger inputs mostly make the runs slow
+const MAX_LEN: usize = 4096;
+
+// to keep the minimization of large inputs from running forever
+const MAX_MINIMIZE_RUNS: usize = 5000;
+
+// Threads of hanging cases keep running (and using a core) until they finish, if ever. Fuzzing
+// refuses to start once this many of them are still around.
+pub const MAX_HUNG_THREADS: usize = 8;
+
+static HUNG_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+// some generic inputs to start from, when there is no corpus for a day
+const DEFAULT_SEEDS: [&str; 6] = [
+    "",
+    "1 2\n3 4\n",
+    "1,2,3\n4,5,6\n",
+    "#####\n#S.E#\n#####\n",
+    "ab-cd\nef-gh\n",
+    "a: 1\nb: 2\n\n3,4\n",
+];
+
+const EXTRA_CHARS: [char; 10] = ['\n', ' ', '0', '9', '-', ',', ':', '#', '.', 'é'];
+
+const INTERESTING_NUMBERS: [&str; 7] = [
+    "0",
+    "-1",
+    "255",
+    "65536",
+    "4294967296",
+    "9223372036854775808",
+    "99999999999999999999999999999999999999999",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Ok,
+    Panic { message: String, location: String },
+    // did not finish within the time budget
+    Hang,
+}
+
+impl Outcome {
+    // the same bug, even if details of the message (like an index) differ
+    fn same_crash(&self, other: &Outcome) -> bool {
+        match (self, other) {
+            (Outcome::Panic { location: a, .. }, Outcome::Panic { location: b, .. }) => a == b,
+            (a, b) => a == b,
+        }
+    }
+}
+
+thread_local! {
+    // message and location of the last panic on this thread
+    static LAST_PANIC: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+}
+
+type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+// Puts the previous panic hook back when dropped.
+pub struct PanicCapture {
+    previous: Option<PanicHook>,
+}
+
+impl Drop for PanicCapture {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            panic::set_hook(previous);
+        }
+    }
+}
+
+// Keeps panics from being printed and remembers them for `run_case` instead, as long as the
+// returned guard lives.
+pub fn capture_panics() -> PanicCapture {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        let message = info.payload_as_str().unwrap_or("panic").to_owned();
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}", l.file(), l.line()))
+            .unwrap_or_default();
+        LAST_PANIC.with(|last| *last.borrow_mut() = Some((message, location)));
+    }));
+    PanicCapture {
+        previous: Some(previous),
+    }
+}
+
+// the threads of hanging cases that have not finished yet
+pub fn hung_threads() -> usize {
+    HUNG_THREADS.load(Ordering::SeqCst)
+}
+
+// Runs a day on its own thread, so panics and endless loops can be detected. Without a timeout it
+// waits for the day to finish. A thread that does not finish within the timeout is leaked, there
+// is no way to stop it, it is counted in `hung_threads` until it finishes on its own.
+//
+// The budget is wall-clock time, not a number of steps: the days are plain functions that cannot
+// be interrupted or made to count their steps without changing every one of them (and counting
+// allocations misses loops that do not allocate). So whether a slow input counts as a hang
+// depends on the machine and its load. The random inputs are reproducible with the same seed, the
+// hangs only if the timeout (`aoc fuzz --timeout`) leaves enough room.
+pub fn run_case(solver: Solver, input: &str, timeout: Option<Duration>) -> Outcome {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_owned();
+    thread::Builder::new()
+        .name("fuzz".to_owned())
+        .spawn(move || {
+            let outcome = match panic::catch_unwind(|| solver(&input)) {
+                Ok(_) => Outcome::Ok,
+                Err(_) => {
+                    let (message, location) = LAST_PANIC
+                        .with(|last| last.borrow_mut().take())
+                        .unwrap_or_else(|| ("unknown panic".to_owned(), String::new()));
+                    Outcome::Panic { message, location }
+                }
+            };
+            // the receiver is only gone if the case was given up as a hang
+            if sender.send(outcome).is_err() {
+                HUNG_THREADS.fetch_sub(1, Ordering::SeqCst);
+            }
+        })
+        .expect("unable to start a thread for fuzzing");
+    let outcome = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).ok(),
+        None => receiver.recv().ok(),
+    };
+    outcome.unwrap_or_else(|| {
+        // counted before the receiver is dropped, so the thread cannot count itself out first
+        HUNG_THREADS.fetch_add(1, Ordering::SeqCst);
+        drop(receiver);
+        Outcome::Hang
+    })
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Crash {
+    pub input: String,
+    pub outcome: Outcome,
+}
+
+// Mutates the corpus at random and collects inputs that panic or hang, one per crash location.
+// Fuzzing stops at the first hang, because the hanging thread keeps running in the background.
+pub fn fuzz(
+    solver: Solver,
+    corpus: &[String],
+    iterations: usize,
+    rng: &mut Rng,
+    timeout: Option<Duration>,
+) -> Result<Vec<Crash>, String> {
+    if hung_threads() >= MAX_HUNG_THREADS {
+        return Err(format!(
+            "{} hanging inputs are still running, not starting any more",
+            hung_threads()
+        ));
+    }
+    let seeds: Vec<String> = if corpus.is_empty() {
+        DEFAULT_SEEDS.iter().map(|s| s.to_string()).collect()
+    } else {
+        corpus.to_vec()
+    };
+    let mut alphabet: Vec<char> = seeds
+        .iter()
+        .flat_map(|seed| seed.chars())
+        .chain(EXTRA_CHARS)
+        .collect::<HashSet<char>>()
+        .into_iter()
+        .collect();
+    alphabet.sort_unstable();
+
+    let mut crashes: Vec<Crash> = Vec::with_capacity(8);
+    for i in 0..iterations {
+        // the unchanged seeds first, then mutations of them
+        let input = if i < seeds.len() {
+            seeds[i].clone()
+        } else {
+            let seed = rng.choose(&seeds);
+            mutate(rng, seed, &alphabet)
+        };
+        let outcome = run_case(solver, &input, timeout);
+        if outcome == Outcome::Ok || crashes.iter().any(|c| c.outcome.same_crash(&outcome)) {
+            continue;
+        }
+        let hang = outcome == Outcome::Hang;
+        crashes.push(Crash { input, outcome });
+        if hang {
+            break;
+        }
+    }
+    Ok(crashes)
+}
+
+pub fn mutate(rng: &mut Rng, seed: &str, alphabet: &[char]) -> String {
+    let mut chars: Vec<char> = seed.chars().collect();
+    for _ in 0..rng.range_usize(1..5) {
+        let pos = rng.range_usize(0..chars.len() + 1);
+        let end = (pos + rng.range_usize(1..9)).min(chars.len());
+        match rng.below(8) {
+            0 => chars.insert(pos, *rng.choose(alphabet)),
+            1 => {
+                chars.drain(pos..end);
+            }
+            2 if pos < chars.len() => chars[pos] = *rng.choose(alphabet),
+            3 => {
+                let copy: Vec<char> = chars[pos..end].to_vec();
+                chars.splice(pos..pos, copy);
+            }
+            4 => chars.truncate(pos),
+            5 => {
+                let number = rng.choose(&INTERESTING_NUMBERS);
+                chars.splice(pos..pos, number.chars());
+            }
+            6 | 7 => {
+                // remove or duplicate the line around `pos`
+                let start = chars[..pos]
+                    .iter()
+                    .rposition(|c| *c == '\n')
+                    .map(|i| i + 1)
+                    .unwrap_or(0);
+                let end = chars[pos..]
+                    .iter()
+                    .position(|c| *c == '\n')
+                    .map(|i| pos + i + 1)
+                    .unwrap_or(chars.len());
+                if rng.chance(1, 2) {
+                    chars.drain(start..end);
+                } else {
+                    let line: Vec<char> = chars[start..end].to_vec();
+                    chars.splice(start..start, line);
+                }
+            }
+            _ => (),
+        }
+    }
+    chars.truncate(MAX_LEN);
+    chars.into_iter().collect()
+}
+
+// Removes ever smaller chunks from a crashing input as long as it keeps crashing the same way.
+// Hangs are not minimized, every attempt would leave another thread running.
+pub fn minimize(solver: Solver, crash: &Crash, timeout: Option<Duration>) -> String {
+    if crash.outcome == Outcome::Hang {
+        return crash.input.clone();
+    }
+    let mut chars: Vec<char> = crash.input.chars().collect();
+    let mut chunk = chars.len().div_ceil(2);
+    let mut runs = 0;
+    while chunk > 0 && runs < MAX_MINIMIZE_RUNS {
+        let mut start = 0;
+        let mut removed = false;
+        while start < chars.len() && runs < MAX_MINIMIZE_RUNS {
+            let end = (start + chunk).min(chars.len());
+            let candidate: String = chars[..start].iter().chain(&chars[end..]).collect();
+            runs += 1;
+            if run_case(solver, &candidate, timeout).same_crash(&crash.outcome) {
+                chars = candidate.chars().collect();
+                removed = true;
+            } else {
+                start += chunk;
+            }
+        }
+        if !removed {
+            chunk /= 2;
+        }
+    }
+    chars.into_iter().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+    use std::fs::{read_dir, read_to_string};
+    use std::sync::Mutex;
+
+    // the panic hook is global, the tests that replace it take turns
+    static HOOK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn saved_crashes_stay_fixed() {
+        // given
+        let Ok(entries) = read_dir(REGRESSIONS) else {
+            return;
+        };
+        let _hook = HOOK.lock().unwrap_or_else(|e| e.into_inner());
+        let _capture = capture_panics();
+
+        for entry in entries {
+            let path = entry.expect("expected directory entry").path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let Some(day) = name.strip_prefix("day-").and_then(|n| n.get(..2)) else {
+                continue;
+            };
+            let day: u8 = day.parse().expect("expected day number in file name");
+            let input = read_to_string(&path).expect("expected crash input to be readable");
+
+            // when
+            let outcome = run_case(
+                days::solver(day).expect("expected day to exist"),
+                &input,
+                None,
+            );
+
+            // then
+            assert_eq!(outcome, Outcome::Ok, "{name} still crashes");
+        }
+    }
+
+    #[test]
+    fn minimize_keeps_the_crash() {
+        // given
+        fn solver(input: &str) -> Result<common::Answers, common::ParseError> {
+            assert!(!input.contains('!'), "exclamation");
+            Err(common::ParseError::new(input, "nothing"))
+        }
+        let _hook = HOOK.lock().unwrap_or_else(|e| e.into_inner());
+        let _capture = capture_panics();
+        let crash = Crash {
+            input: "some text! and more".to_owned(),
+            outcome: run_case(solver, "!", None),
+        };
+
+        // when
+        let minimized = minimize(solver, &crash, None);
+
+        // then
+        assert_eq!(minimized, "!");
+    }
+
+    #[test]
+    fn hung_threads_are_counted_until_they_finish() {
+        // given
+        static GATE: Mutex<()> = Mutex::new(());
+        fn solver(input: &str) -> Result<common::Answers, common::ParseError> {
+            drop(GATE.lock());
+            Err(common::ParseError::new(input, "nothing"))
+        }
+        let gate = GATE.lock().unwrap_or_else(|e| e.into_inner());
+
+        // when
+        let outcome = run_case(solver, "", Some(Duration::ZERO));
+
+        // then
+        assert_eq!(outcome, Outcome::Hang);
+        assert_eq!(hung_threads(), 1);
+        drop(gate);
+        while hung_threads() > 0 {
+            thread::yield_now();
+        }
+    }
+}
@@ -0,0 +1,783 @@
+// not `forbid`, the allocation counter for the benchmarks needs to opt out
+#![deny(unsafe_code)]
+
+use common::random::{self, Rng};
+use common::{Answer, Answers};
+use registry::Registry;
+use std::env;
+use std::fs::{create_dir_all, read_dir, read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+mod alloc;
+mod bench;
+mod days;
+mod fuzz;
+mod registry;
+mod verify;
+
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAllocator = alloc::CountingAllocator;
+
+const USAGE: &str = r#"Usage:
+    aoc run <day> <input file>
+    aoc run <day|all> --inputs <directory>
+    aoc bench <day|all> (<input file> | --inputs <directory>) [options]
+    aoc verify <day|all> (<input file> | --inputs <directory>) [--answers <file>] [--record]
+    aoc fuzz <day|all> [options]
+    aoc gen <day|all> [options]
+
+The input directory is expected to contain one file per day, named day-01.txt to day-25.txt.
+
+Global options:
+    --threads <n>       threads for the parallel parts of days 6, 7, 13, 20 and 22 (default one
+                        per core), more than one needs the 'parallel' feature
+
+Benchmark options:
+    --runs <n>          number of runs per phase (default 10)
+    --json              print the results as JSON, e.g. to store them as baseline
+    --baseline <file>   compare the medians with a previous JSON result and fail on regressions
+
+Verify options:
+    --answers <file>    the known answers (default answers.toml next to the inputs)
+    --record            add the answers of inputs without known answers to the answers file
+
+Fuzz options:
+    --iterations <n>    number of inputs per day (default 10000)
+    --seed <n>          seed for the random mutations (default 0)
+    --corpus <dir>      inputs to mutate, all files whose name starts with day-NN
+    --timeout <ms>      wall-clock time per input before it counts as endless loop (default 2000),
+                        hangs close to it depend on the load of the machine
+
+Crashing inputs are minimized and saved as regression tests in aoc/fuzz/regressions.
+
+Gen options:
+    --seed <n>          seed for the random input, the same seed gives the same input (default 0)
+    --size <n>          main dimension of the input (lines, width, ...), default like real inputs
+    --swaps <n>         pairs of gates with swapped outputs for day 24 (default 4)
+    --output <dir>      write day-NN.txt files instead of printing, required for all days"#;
+
+fn main() -> Result<(), String> {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    if let Some(threads) = take_threads(&mut args)? {
+        common::set_threads(threads)?;
+    }
+    match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("bench") => run_benchmarks(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("fuzz") => run_fuzzer(&args[1..]),
+        Some("gen") => generate(&args[1..]),
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some(command) => Err(format!("unknown command '{command}'\n\n{USAGE}")),
+        None => Err(USAGE.to_owned()),
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (days, inputs) = parse_run_args(args)?;
+
+    let mut failed: Vec<u8> = Vec::with_capacity(days.len());
+    for day in days {
+        let path = match day_input(&inputs, day) {
+            Ok(path) => path,
+            Err(missing) => {
+                println!("Day {day:02}: skipped, no input at {}", missing.display());
+                continue;
+            }
+        };
+        println!("Day {day:02}");
+        match run_day(day, &path) {
+            Ok(Answers { part1, part2 }) => {
+                let part1_ok = print_part(1, &part1);
+                let part2_ok = print_part(2, &part2);
+                if !(part1_ok && part2_ok) {
+                    failed.push(day);
+                }
+            }
+            Err(e) => {
+                println!("  Error: {e}");
+                failed.push(day);
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("failed days: {failed:?}"))
+    }
+}
+
+fn print_part(part: u8, answer: &Result<Answer, String>) -> bool {
+    match answer {
+        Ok(Answer::Unsolved) => true,
+        Ok(answer) => {
+            println!("  Part {part}: {answer}");
+            true
+        }
+        Err(e) => {
+            println!("  Part {part} failed: {e}");
+            false
+        }
+    }
+}
+
+fn run_day(day: u8, path: &Path) -> Result<Answers, String> {
+    let solver = days::solver(day).ok_or_else(|| format!("there is no day {day}"))?;
+    solver(&read_input(path)?).map_err(|e| e.to_string())
+}
+
+fn run_benchmarks(args: &[String]) -> Result<(), String> {
+    let (days, inputs, options) = parse_bench_args(args)?;
+    let baseline = match &options.baseline {
+        Some(path) => Some(bench::parse_baseline(&read_input(path)?)?),
+        None => None,
+    };
+
+    let mut results: Vec<bench::DayBenchmark> = Vec::with_capacity(days.len());
+    for day in days {
+        let path = match day_input(&inputs, day) {
+            Ok(path) => path,
+            Err(missing) => {
+                eprintln!("Day {day:02}: skipped, no input at {}", missing.display());
+                continue;
+            }
+        };
+        let benchmark = days::benchmark(day).ok_or_else(|| format!("there is no day {day}"))?;
+        let result = benchmark(&read_input(&path)?, options.runs).map_err(|e| e.to_string())?;
+        results.push(result);
+    }
+
+    if options.json {
+        println!("{}", bench::to_json(options.runs, &results));
+    } else {
+        print!("{}", bench::format_table(&results));
+    }
+
+    if let Some(baseline) = baseline {
+        let comparisons = bench::compare(&baseline, &results);
+        let table = bench::format_comparison(&comparisons);
+        // keep stdout valid JSON
+        if options.json {
+            eprint!("{table}");
+        } else {
+            println!();
+            print!("{table}");
+        }
+        let regressions: Vec<String> = comparisons
+            .iter()
+            .filter(|c| c.is_regression())
+            .map(|c| format!("day {:02} {}", c.day, c.phase))
+            .collect();
+        if !regressions.is_empty() {
+            return Err(format!("regressions in {}", regressions.join(", ")));
+        }
+    }
+    Ok(())
+}
+
+fn verify(args: &[String]) -> Result<(), String> {
+    let (days, inputs, options) = parse_verify_args(args)?;
+    let answers_path = options
+        .answers
+        .clone()
+        .unwrap_or_else(|| default_answers_path(&inputs));
+    let mut registry = if answers_path.is_file() {
+        let content = read_input(&answers_path)?;
+        Registry::parse(&content).map_err(|e| {
+            format!(
+                "unable to read answers from '{}': {}",
+                answers_path.display(),
+                e.locate(&content)
+            )
+        })?
+    } else if options.record {
+        Registry::default()
+    } else {
+        return Err(format!("no answers file at '{}'", answers_path.display()));
+    };
+
+    let (mut passed, mut missing, mut recorded) = (0, 0, 0);
+    let mut failed: Vec<String> = Vec::with_capacity(2 * days.len());
+    for day in days {
+        let path = match day_input(&inputs, day) {
+            Ok(path) => path,
+            Err(missing) => {
+                println!("Day {day:02}: skipped, no input at {}", missing.display());
+                continue;
+            }
+        };
+        let content = read_input(&path)?;
+        let hash = registry::input_hash(&content);
+        let verifier = days::verifier(day).ok_or_else(|| format!("there is no day {day}"))?;
+        let parts = match verifier(&content) {
+            Ok(parts) => parts,
+            Err(e) => {
+                println!("Day {day:02}: ERROR, {e}");
+                failed.push(format!("day {day:02}"));
+                continue;
+            }
+        };
+        for (part, timed) in (1..=2).zip(parts) {
+            let status = verify::Status::check(registry.get(day, hash, part), &timed.answer);
+            println!("Day {day:02} part {part}: {status} ({:.1?})", timed.time);
+            match status {
+                verify::Status::Pass => passed += 1,
+                verify::Status::Missing(answer) if options.record => {
+                    registry.insert(day, hash, part, answer);
+                    recorded += 1;
+                }
+                verify::Status::Missing(_) => missing += 1,
+                status if status.is_failure() => failed.push(format!("day {day:02} part {part}")),
+                _ => (),
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{passed} passed, {} failed, {missing} missing, {recorded} recorded",
+        failed.len()
+    );
+    if recorded > 0 {
+        write(&answers_path, registry.to_toml())
+            .map_err(|e| format!("unable to write '{}': {e}", answers_path.display()))?;
+    }
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("failed: {}", failed.join(", ")))
+    }
+}
+
+// next to the input file, or inside the input directory
+fn default_answers_path(inputs: &Inputs) -> PathBuf {
+    let dir = match inputs {
+        Inputs::File(path) => path.parent().unwrap_or(Path::new("")),
+        Inputs::Directory(dir) => dir,
+    };
+    dir.join("answers.toml")
+}
+
+fn run_fuzzer(args: &[String]) -> Result<(), String> {
+    let (days, options) = parse_fuzz_args(args)?;
+    let timeout = Duration::from_millis(options.timeout_ms);
+    let mut rng = Rng::new(options.seed);
+    let _capture = fuzz::capture_panics();
+
+    let mut crashed: Vec<u8> = Vec::with_capacity(days.len());
+    for day in days {
+        let solver = days::solver(day).ok_or_else(|| format!("there is no day {day}"))?;
+        let mut corpus = read_corpus(Path::new(fuzz::REGRESSIONS), day)?;
+        if let Some(dir) = &options.corpus {
+            corpus.extend(read_corpus(dir, day)?);
+        }
+        let crashes = fuzz::fuzz(solver, &corpus, options.iterations, &mut rng, Some(timeout))?;
+        if crashes.is_empty() {
+            println!("Day {day:02}: no crashes");
+            continue;
+        }
+        crashed.push(day);
+        for crash in crashes {
+            match &crash.outcome {
+                fuzz::Outcome::Panic { message, location } => {
+                    println!("Day {day:02}: panic at {location}: {message}");
+                }
+                fuzz::Outcome::Hang => {
+                    println!("Day {day:02}: no result within {timeout:?} (see --timeout)")
+                }
+                fuzz::Outcome::Ok => (),
+            }
+            let input = fuzz::minimize(solver, &crash, Some(timeout));
+            let path = Path::new(fuzz::REGRESSIONS).join(format!(
+                "day-{day:02}-{:016x}.txt",
+                registry::input_hash(&input)
+            ));
+            create_dir_all(fuzz::REGRESSIONS)
+                .and_then(|_| write(&path, &input))
+                .map_err(|e| format!("unable to write '{}': {e}", path.display()))?;
+            println!("  saved as {}", path.display());
+        }
+    }
+
+    if crashed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("crashes in days {crashed:?}"))
+    }
+}
+
+fn generate(args: &[String]) -> Result<(), String> {
+    let (days, options) = parse_gen_args(args)?;
+    for day in days {
+        let generator = days::generator(day).ok_or_else(|| format!("there is no day {day}"))?;
+        // a fresh generator per day, so a day gets the same input when generated on its own
+        let input = generator(&mut Rng::new(options.seed), &options.generator)
+            .ok_or_else(|| format!("day {day} has no input generator"))?;
+        match &options.output {
+            Some(dir) => {
+                let path = input_path(dir, day);
+                create_dir_all(dir)
+                    .and_then(|_| write(&path, &input))
+                    .map_err(|e| format!("unable to write '{}': {e}", path.display()))?;
+                println!("Day {day:02}: {}", path.display());
+            }
+            None => print!("{input}"),
+        }
+    }
+    Ok(())
+}
+
+// all files in `dir` whose name starts with day-NN
+fn read_corpus(dir: &Path, day: u8) -> Result<Vec<String>, String> {
+    let Ok(entries) = read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let prefix = format!("day-{day:02}");
+    let mut corpus: Vec<String> = Vec::with_capacity(8);
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let matches = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(&prefix));
+        if matches && path.is_file() {
+            corpus.push(read_input(&path)?);
+        }
+    }
+    Ok(corpus)
+}
+
+fn read_input(path: &Path) -> Result<String, String> {
+    read_to_string(path).map_err(|e| format!("unable to read '{}': {e}", path.display()))
+}
+
+// the input file of a day, or the path where it is missing
+fn day_input(inputs: &Inputs, day: u8) -> Result<PathBuf, PathBuf> {
+    match inputs {
+        Inputs::File(path) => Ok(path.clone()),
+        Inputs::Directory(dir) => {
+            let path = input_path(dir, day);
+            if path.is_file() {
+                Ok(path)
+            } else {
+                Err(path)
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Inputs {
+    File(PathBuf),
+    Directory(PathBuf),
+}
+
+fn parse_run_args(args: &[String]) -> Result<(Vec<u8>, Inputs), String> {
+    let mut day: Option<&str> = None;
+    let mut inputs: Option<Inputs> = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--inputs" {
+            let dir = args
+                .next()
+                .ok_or_else(|| "expected a directory after '--inputs'".to_owned())?;
+            inputs = Some(Inputs::Directory(PathBuf::from(dir)));
+        } else if day.is_none() {
+            day = Some(arg);
+        } else if inputs.is_none() {
+            inputs = Some(Inputs::File(PathBuf::from(arg)));
+        } else {
+            return Err(format!("unexpected argument '{arg}'\n\n{USAGE}"));
+        }
+    }
+
+    let days = parse_days(day.ok_or_else(|| format!("no day given\n\n{USAGE}"))?)?;
+    let inputs = inputs.ok_or_else(|| format!("no input given\n\n{USAGE}"))?;
+    if days.len() > 1 && matches!(inputs, Inputs::File(_)) {
+        return Err("running all days requires an input directory ('--inputs')".to_owned());
+    }
+    Ok((days, inputs))
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct BenchOptions {
+    runs: usize,
+    json: bool,
+    baseline: Option<PathBuf>,
+}
+
+fn parse_bench_args(args: &[String]) -> Result<(Vec<u8>, Inputs, BenchOptions), String> {
+    let mut options = BenchOptions {
+        runs: 10,
+        json: false,
+        baseline: None,
+    };
+    let mut rest: Vec<String> = Vec::with_capacity(args.len());
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => {
+                let runs = args
+                    .next()
+                    .ok_or_else(|| "expected a number after '--runs'".to_owned())?;
+                options.runs =
+                    runs.parse().ok().filter(|runs| *runs > 0).ok_or_else(|| {
+                        format!("expected a positive number of runs, found '{runs}'")
+                    })?;
+            }
+            "--json" => options.json = true,
+            "--baseline" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "expected a file after '--baseline'".to_owned())?;
+                options.baseline = Some(PathBuf::from(path));
+            }
+            _ => rest.push(arg.clone()),
+        }
+    }
+    let (days, inputs) = parse_run_args(&rest)?;
+    Ok((days, inputs, options))
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct VerifyOptions {
+    answers: Option<PathBuf>,
+    record: bool,
+}
+
+fn parse_verify_args(args: &[String]) -> Result<(Vec<u8>, Inputs, VerifyOptions), String> {
+    let mut options = VerifyOptions {
+        answers: None,
+        record: false,
+    };
+    let mut rest: Vec<String> = Vec::with_capacity(args.len());
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "expected a file after '--answers'".to_owned())?;
+                options.answers = Some(PathBuf::from(path));
+            }
+            "--record" => options.record = true,
+            _ => rest.push(arg.clone()),
+        }
+    }
+    let (days, inputs) = parse_run_args(&rest)?;
+    Ok((days, inputs, options))
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct FuzzOptions {
+    iterations: usize,
+    seed: u64,
+    corpus: Option<PathBuf>,
+    timeout_ms: u64,
+}
+
+fn parse_fuzz_args(args: &[String]) -> Result<(Vec<u8>, FuzzOptions), String> {
+    let mut options = FuzzOptions {
+        iterations: 10_000,
+        seed: 0,
+        corpus: None,
+        timeout_ms: 2000,
+    };
+    let mut day: Option<&str> = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("expected a value after '{name}'"))
+        };
+        match arg.as_str() {
+            "--iterations" => options.iterations = parse_option(arg, value(arg)?)?,
+            "--seed" => options.seed = parse_option(arg, value(arg)?)?,
+            "--corpus" => options.corpus = Some(PathBuf::from(value(arg)?)),
+            "--timeout" => options.timeout_ms = parse_option(arg, value(arg)?)?,
+            _ if day.is_none() => day = Some(arg),
+            _ => return Err(format!("unexpected argument '{arg}'\n\n{USAGE}")),
+        }
+    }
+    let days = parse_days(day.ok_or_else(|| format!("no day given\n\n{USAGE}"))?)?;
+    Ok((days, options))
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct GenOptions {
+    seed: u64,
+    generator: random::GenOptions,
+    output: Option<PathBuf>,
+}
+
+fn parse_gen_args(args: &[String]) -> Result<(Vec<u8>, GenOptions), String> {
+    let mut options = GenOptions {
+        seed: 0,
+        generator: random::GenOptions::default(),
+        output: None,
+    };
+    let mut day: Option<&str> = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("expected a value after '{name}'"))
+        };
+        match arg.as_str() {
+            "--seed" => options.seed = parse_option(arg, value(arg)?)?,
+            "--size" => options.generator.size = Some(parse_option(arg, value(arg)?)?),
+            "--swaps" => options.generator.swaps = Some(parse_option(arg, value(arg)?)?),
+            "--output" => options.output = Some(PathBuf::from(value(arg)?)),
+            _ if day.is_none() => day = Some(arg),
+            _ => return Err(format!("unexpected argument '{arg}'\n\n{USAGE}")),
+        }
+    }
+    let days = parse_days(day.ok_or_else(|| format!("no day given\n\n{USAGE}"))?)?;
+    if days.len() > 1 && options.output.is_none() {
+        return Err("generating all days requires an output directory ('--output')".to_owned());
+    }
+    Ok((days, options))
+}
+
+// `--threads` works with every command, so it is removed before the command is parsed
+fn take_threads(args: &mut Vec<String>) -> Result<Option<usize>, String> {
+    let Some(i) = args.iter().position(|arg| arg == "--threads") else {
+        return Ok(None);
+    };
+    let value = args
+        .get(i + 1)
+        .ok_or_else(|| "expected a value after '--threads'".to_owned())?;
+    let threads = parse_option("--threads", value)?;
+    args.drain(i..i + 2);
+    Ok(Some(threads))
+}
+
+fn parse_option<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("unable to parse '{value}' for '{name}'"))
+}
+
+fn parse_days(day: &str) -> Result<Vec<u8>, String> {
+    if day == "all" {
+        return Ok((1..=days::DAYS.len() as u8).collect());
+    }
+    let day: u8 = day
+        .parse()
+        .map_err(|e| format!("unable to parse day '{day}': {e}"))?;
+    if days::solver(day).is_none() {
+        return Err(format!("there is no day {day}"));
+    }
+    Ok(vec![day])
+}
+
+fn input_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day-{day:02}.txt"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_run_args_works_for_single_day_with_file() {
+        // when
+        let result = parse_run_args(&args(&["16", "input.txt"]));
+
+        // then
+        assert_eq!(
+            result,
+            Ok((vec![16], Inputs::File(PathBuf::from("input.txt"))))
+        );
+    }
+
+    #[test]
+    fn parse_run_args_works_for_all_days_with_directory() {
+        // when
+        let (days, inputs) =
+            parse_run_args(&args(&["all", "--inputs", "dir/"])).expect("expected args to parse");
+
+        // then
+        assert_eq!(days, (1..=25).collect::<Vec<u8>>());
+        assert_eq!(inputs, Inputs::Directory(PathBuf::from("dir/")));
+    }
+
+    #[test]
+    fn parse_run_args_rejects_all_days_with_single_file() {
+        // when
+        let result = parse_run_args(&args(&["all", "input.txt"]));
+
+        // then
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_bench_args_separates_options() {
+        // when
+        let result = parse_bench_args(&args(&[
+            "all",
+            "--runs",
+            "3",
+            "--inputs",
+            "dir/",
+            "--baseline",
+            "old.json",
+        ]));
+
+        // then
+        let (days, inputs, options) = result.expect("expected args to parse");
+        assert_eq!(days.len(), 25);
+        assert_eq!(inputs, Inputs::Directory(PathBuf::from("dir/")));
+        assert_eq!(
+            options,
+            BenchOptions {
+                runs: 3,
+                json: false,
+                baseline: Some(PathBuf::from("old.json")),
+            }
+        );
+        assert!(parse_bench_args(&args(&["1", "in.txt", "--runs", "0"])).is_err());
+    }
+
+    #[test]
+    fn verify_defaults_to_answers_next_to_inputs() {
+        // when
+        let (_, inputs, options) =
+            parse_verify_args(&args(&["3", "inputs/day-03.txt"])).expect("expected args to parse");
+
+        // then
+        assert_eq!(options.answers, None);
+        assert_eq!(
+            default_answers_path(&inputs),
+            PathBuf::from("inputs/answers.toml")
+        );
+    }
+
+    #[test]
+    fn parse_fuzz_args_reads_options() {
+        // when
+        let result = parse_fuzz_args(&args(&["4", "--seed", "7", "--timeout", "100"]));
+
+        // then
+        assert_eq!(
+            result,
+            Ok((
+                vec![4],
+                FuzzOptions {
+                    iterations: 10_000,
+                    seed: 7,
+                    corpus: None,
+                    timeout_ms: 100,
+                }
+            ))
+        );
+        assert!(parse_fuzz_args(&args(&["4", "--seed", "x"])).is_err());
+    }
+
+    #[test]
+    fn parse_gen_args_requires_output_for_all_days() {
+        // when
+        let result = parse_gen_args(&args(&[
+            "18", "--size", "3000", "--seed", "5", "--swaps", "2",
+        ]));
+
+        // then
+        assert_eq!(
+            result,
+            Ok((
+                vec![18],
+                GenOptions {
+                    seed: 5,
+                    generator: random::GenOptions {
+                        size: Some(3000),
+                        swaps: Some(2),
+                    },
+                    output: None,
+                }
+            ))
+        );
+        assert!(parse_gen_args(&args(&["all"])).is_err());
+        assert!(parse_gen_args(&args(&["all", "--output", "inputs/"])).is_ok());
+    }
+
+    #[test]
+    fn generated_inputs_can_be_solved() {
+        for day in 1..=25 {
+            let generator = days::generator(day).expect("expected day to exist");
+            let solver = days::solver(day).expect("expected day to exist");
+            let options = random::GenOptions {
+                size: Some(20),
+                swaps: None,
+            };
+            for seed in 0..2 {
+                // given
+                let input = generator(&mut Rng::new(seed), &options)
+                    .unwrap_or_else(|| panic!("expected a generator for day {day}"));
+
+                // when
+                let answers = solver(&input);
+
+                // then
+                let Ok(Answers { part1, part2 }) = answers else {
+                    panic!("day {day} with seed {seed}: {answers:?}");
+                };
+                assert!(
+                    part1.is_ok(),
+                    "day {day} part 1 with seed {seed}: {part1:?}"
+                );
+                assert!(
+                    part2.is_ok(),
+                    "day {day} part 2 with seed {seed}: {part2:?}"
+                );
+                assert_eq!(generator(&mut Rng::new(seed), &options), Some(input));
+            }
+        }
+    }
+
+    #[test]
+    fn take_threads_removes_the_option() {
+        // given
+        let mut run = args(&["run", "--threads", "3", "all", "--inputs", "dir/"]);
+
+        // when
+        let threads = take_threads(&mut run);
+
+        // then
+        assert_eq!(threads, Ok(Some(3)));
+        assert_eq!(run, args(&["run", "all", "--inputs", "dir/"]));
+        assert!(take_threads(&mut args(&["run", "--threads"])).is_err());
+    }
+
+    #[test]
+    fn parse_days_rejects_unknown_day() {
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("0").is_err());
+    }
+
+    #[test]
+    fn day_solver_works_for_example() {
+        // given
+        let solver = days::solver(1).expect("expected day 1 to exist");
+
+        // when
+        let answers = solver("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n");
+
+        // then
+        assert_eq!(
+            answers,
+            Ok(Answers {
+                part1: Ok(Answer::Number(11)),
+                part2: Ok(Answer::Number(31)),
+            })
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use common::{expect_prefix, expect_split, parse_number, Answer, Json, ParseError};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+// Identifies an input file, so answers for different inputs of the same day can be kept apart.
+pub type InputHash = u64;
+
+// FNV-1a over the input, ignoring carriage returns and trailing whitespace, so the hash does not
+// depend on how the file was saved. Unlike `DefaultHasher` it is stable across rust versions.
+pub fn input_hash(content: &str) -> InputHash {
+    content
+        .trim_end()
+        .bytes()
+        .filter(|b| *b != b'\r')
+        .fold(0xcbf29ce484222325, |hash, b| {
+            (hash ^ b as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+// The known answers for real puzzle inputs, stored as a small subset of TOML:
+//
+//     [day-01.8d2b6a0f3c1e4d57]
+//     part1 = 1234
+//     part2 = "co,de,ka,ta"
+//
+// Comments are allowed on lines of their own.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Registry {
+    answers: BTreeMap<(u8, InputHash), [Option<Answer>; 2]>,
+}
+
+impl Registry {
+    pub fn get(&self, day: u8, hash: InputHash, part: u8) -> Option<&Answer> {
+        self.answers.get(&(day, hash))?[part_index(part)].as_ref()
+    }
+
+    pub fn insert(&mut self, day: u8, hash: InputHash, part: u8, answer: Answer) {
+        self.answers.entry((day, hash)).or_default()[part_index(part)] = Some(answer);
+    }
+
+    pub fn parse(content: &str) -> Result<Registry, ParseError> {
+        let mut registry = Registry::default();
+        let mut section: Option<(u8, InputHash)> = None;
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[') {
+                let header = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| ParseError::after(line, "']'"))?;
+                let (day, hash) = expect_split(header, ".")?;
+                let day = parse_number(expect_prefix(day, "day-")?)?;
+                if hash.len() != 16 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err(ParseError::new(hash, "an input hash of 16 hex digits"));
+                }
+                let hash = InputHash::from_str_radix(hash, 16)
+                    .map_err(|_| ParseError::new(hash, "an input hash of 16 hex digits"))?;
+                section = Some((day, hash));
+                continue;
+            }
+
+            let (key, value) = expect_split(line, "=")?;
+            let key = key.trim();
+            let part = match key {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(ParseError::new(key, "'part1' or 'part2'")),
+            };
+            let (day, hash) = section.ok_or_else(|| {
+                ParseError::new(key, "a section header like [day-01.<input hash>]")
+            })?;
+            // integers and basic strings are written the same way in TOML and JSON
+            let value = value.trim();
+            let answer = match Json::parse(value)? {
+                Json::Integer(n) => Answer::Number(n),
+                Json::String(s) => Answer::Text(s),
+                _ => return Err(ParseError::new(value, "an integer or a string")),
+            };
+            registry.insert(day, hash, part, answer);
+        }
+        Ok(registry)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut out = String::with_capacity(64 * self.answers.len());
+        for ((day, hash), answers) in &self.answers {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            let _ = writeln!(out, "[day-{day:02}.{hash:016x}]");
+            for (part, answer) in (1..=2).zip(answers) {
+                match answer {
+                    Some(Answer::Number(n)) => {
+                        let _ = writeln!(out, "part{part} = {n}");
+                    }
+                    Some(Answer::Text(s)) => {
+                        let _ = writeln!(out, "part{part} = {}", Json::from(s.as_str()));
+                    }
+                    Some(Answer::Unsolved) | None => (),
+                }
+            }
+        }
+        out
+    }
+}
+
+fn part_index(part: u8) -> usize {
+    assert!(part == 1 || part == 2, "there is no part {part}");
+    part as usize - 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registry_round_trips_through_toml() {
+        // given
+        let content = "# known answers\n[day-23.00000000000000ff]\npart1 = 7\npart2 = \"co,de,\\\"ka\\\"\"\n\n[day-01.0123456789abcdef]\npart1 = -11\n";
+
+        // when
+        let registry = Registry::parse(content).expect("expected registry to parse");
+
+        // then
+        assert_eq!(registry.get(23, 0xff, 1), Some(&Answer::Number(7)));
+        assert_eq!(
+            registry.get(23, 0xff, 2),
+            Some(&Answer::Text("co,de,\"ka\"".to_owned()))
+        );
+        assert_eq!(registry.get(1, 0x0123456789abcdef, 2), None);
+        assert_eq!(Registry::parse(&registry.to_toml()), Ok(registry));
+    }
+
+    #[test]
+    fn parse_rejects_answers_outside_of_sections() {
+        // given
+        let content = "part1 = 7\n";
+
+        // when
+        let error = Registry::parse(content)
+            .expect_err("expected answer without section to be rejected")
+            .locate(content);
+
+        // then
+        assert_eq!(error.location.map(|l| l.line), Some(1));
+    }
+
+    #[test]
+    fn input_hash_ignores_line_endings() {
+        assert_eq!(input_hash("1 2\n3 4\n"), input_hash("1 2\r\n3 4"));
+        assert_ne!(input_hash("1 2\n3 4\n"), input_hash("1 2\n3 5\n"));
+    }
+}
@@ -0,0 +1,87 @@
+use common::{Answer, ParseError, Solution};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TimedAnswer {
+    pub answer: Result<Answer, String>,
+    pub time: Duration,
+}
+
+pub type Verifier = fn(&str) -> Result<[TimedAnswer; 2], ParseError>;
+
+pub fn solve_timed<S: Solution>(content: &str) -> Result<[TimedAnswer; 2], ParseError> {
+    let input = common::parse::<S>(content)?;
+    Ok([timed(|| S::part1(&input)), timed(|| S::part2(&input))])
+}
+
+fn timed(part: impl FnOnce() -> Result<Answer, String>) -> TimedAnswer {
+    let start = Instant::now();
+    let answer = part();
+    TimedAnswer {
+        answer,
+        time: start.elapsed(),
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Status {
+    Pass,
+    Fail { expected: Answer, found: Answer },
+    // there is no known answer for this input yet
+    Missing(Answer),
+    // the part has no code solution, so there is nothing to check
+    Unsolved,
+    Error(String),
+}
+
+impl Status {
+    pub fn check(expected: Option<&Answer>, answer: &Result<Answer, String>) -> Status {
+        match (answer, expected) {
+            (Err(e), _) => Status::Error(e.clone()),
+            (Ok(Answer::Unsolved), _) => Status::Unsolved,
+            (Ok(found), None) => Status::Missing(found.clone()),
+            (Ok(found), Some(expected)) if found == expected => Status::Pass,
+            (Ok(found), Some(expected)) => Status::Fail {
+                expected: expected.clone(),
+                found: found.clone(),
+            },
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Fail { .. } | Status::Error(_))
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected, found } => {
+                write!(f, "FAIL, expected {expected}, found {found}")
+            }
+            Status::Missing(found) => write!(f, "missing, found {found}"),
+            Status::Unsolved => write!(f, "unsolved"),
+            Status::Error(e) => write!(f, "ERROR, {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_compares_with_known_answer() {
+        // given
+        let known = Answer::Number(42);
+
+        // then
+        assert_eq!(Status::check(Some(&known), &Ok(42u8.into())), Status::Pass);
+        assert!(Status::check(Some(&known), &Ok(41u8.into())).is_failure());
+        assert!(Status::check(Some(&known), &Err("no way".to_owned())).is_failure());
+        assert_eq!(Status::check(None, &Ok(Answer::Unsolved)), Status::Unsolved);
+        assert!(!Status::check(None, &Ok(7u8.into())).is_failure());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::Picture;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+// One state of a simulation, with a line of text below the picture.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Frame {
+    pub picture: Picture,
+    pub caption: String,
+}
+
+// Frames are produced lazily, so long simulations on large maps do not have to fit into memory.
+pub type Animation<'a> = Box<dyn Iterator<Item = Frame> + 'a>;
+
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+const HELP: &str = "enter: step, p: play/pause, +/-: faster/slower, <n>: skip n frames, q: quit";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Command {
+    // pauses and shows the next frame
+    Step,
+    Skip(usize),
+    TogglePlay,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Command {
+    // The controls are whole lines, that works in any terminal without switching it to raw mode.
+    pub fn parse(line: &str) -> Option<Command> {
+        match line.trim() {
+            "" | "s" => Some(Command::Step),
+            "p" => Some(Command::TogglePlay),
+            "+" => Some(Command::Faster),
+            "-" => Some(Command::Slower),
+            "q" => Some(Command::Quit),
+            n => n.parse().ok().map(Command::Skip),
+        }
+    }
+}
+
+// Reads commands from stdin on a thread of its own, unknown commands are ignored. The channel is
+// closed at the end of stdin.
+pub fn stdin_controls() -> Receiver<Command> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if let Some(command) = Command::parse(&line) {
+                if sender.send(command).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Player {
+    // between two frames while playing
+    pub delay: Duration,
+    pub playing: bool,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Player {
+            delay: Duration::from_millis(100),
+            playing: false,
+        }
+    }
+}
+
+impl Player {
+    // Shows the frames one after the other until the last one or until the user quits. Once the
+    // controls are closed (e.g. stdin is not a terminal) the remaining frames are played.
+    // Returns the number of the last frame shown.
+    pub fn play(
+        &mut self,
+        mut frames: Animation<'_>,
+        controls: &Receiver<Command>,
+        out: &mut impl Write,
+    ) -> io::Result<usize> {
+        let Some(mut frame) = frames.next() else {
+            return Ok(0);
+        };
+        let mut number = 1;
+        let mut controls_open = true;
+        loop {
+            self.draw(&frame, number, out)?;
+            let command = if !controls_open {
+                thread::sleep(self.delay);
+                None
+            } else if self.playing {
+                match controls.recv_timeout(self.delay) {
+                    Ok(command) => Some(command),
+                    Err(RecvTimeoutError::Timeout) => None,
+                    Err(RecvTimeoutError::Disconnected) => {
+                        controls_open = false;
+                        None
+                    }
+                }
+            } else {
+                match controls.recv() {
+                    Ok(command) => Some(command),
+                    Err(_) => {
+                        controls_open = false;
+                        self.playing = true;
+                        continue;
+                    }
+                }
+            };
+
+            let skip = match command {
+                None | Some(Command::Skip(0)) => 1,
+                Some(Command::Step) => {
+                    self.playing = false;
+                    1
+                }
+                Some(Command::Skip(n)) => n,
+                Some(Command::TogglePlay) => {
+                    self.playing = !self.playing;
+                    continue;
+                }
+                Some(Command::Faster) => {
+                    self.delay = (self.delay / 2).max(MIN_DELAY);
+                    continue;
+                }
+                Some(Command::Slower) => {
+                    self.delay = (self.delay * 2).min(MAX_DELAY);
+                    continue;
+                }
+                Some(Command::Quit) => return Ok(number),
+            };
+            match frames.nth(skip - 1) {
+                Some(next) => {
+                    frame = next;
+                    number += skip;
+                }
+                None => return Ok(number),
+            }
+        }
+    }
+
+    fn draw(&self, frame: &Frame, number: usize, out: &mut impl Write) -> io::Result<()> {
+        let state = if self.playing { "playing" } else { "paused" };
+        let fps = 1.0 / self.delay.as_secs_f64();
+        // move to the top left corner and clear the screen
+        write!(out, "\x1b[H\x1b[2J")?;
+        write!(out, "{}", frame.picture.to_ansi())?;
+        writeln!(out, "{}", frame.caption)?;
+        writeln!(out, "frame {number}, {state} at {fps:.1} fps ({HELP})")?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Cell;
+
+    fn frames(n: usize) -> Animation<'static> {
+        Box::new((0..n).map(|i| Frame {
+            picture: Picture::from_fn(2, 1, |_| Cell::new('x')),
+            caption: format!("step {i}"),
+        }))
+    }
+
+    #[test]
+    fn command_parse_reads_controls() {
+        assert_eq!(Command::parse("\n"), Some(Command::Step));
+        assert_eq!(Command::parse(" 25 "), Some(Command::Skip(25)));
+        assert_eq!(Command::parse("+"), Some(Command::Faster));
+        assert_eq!(Command::parse("x"), None);
+    }
+
+    #[test]
+    fn player_follows_the_controls() {
+        // given
+        let (sender, controls) = mpsc::channel();
+        for command in [
+            Command::Step,
+            Command::Skip(2),
+            Command::Faster,
+            Command::Quit,
+        ] {
+            sender.send(command).expect("expected channel to be open");
+        }
+        let mut player = Player::default();
+        let mut out: Vec<u8> = Vec::new();
+
+        // when
+        let last = player.play(frames(10), &controls, &mut out);
+
+        // then
+        let out = String::from_utf8(out).expect("expected output to be UTF-8");
+        assert_eq!(last.ok(), Some(4));
+        assert!(out.contains("step 1\n") && out.contains("step 3\n"));
+        assert!(!out.contains("step 2\n"));
+        assert_eq!(player.delay, Duration::from_millis(50));
+    }
+
+    #[test]
+    fn player_plays_to_the_end_without_controls() {
+        // given
+        let (sender, controls) = mpsc::channel::<Command>();
+        drop(sender);
+        let mut player = Player {
+            delay: Duration::ZERO,
+            playing: false,
+        };
+        let mut out: Vec<u8> = Vec::new();
+
+        // when
+        let last = player.play(frames(5), &controls, &mut out);
+
+        // then
+        assert_eq!(last.ok(), Some(5));
+    }
+}
@@ -0,0 +1,490 @@
+use crate::{parse, set_threads, stdin_controls, Answer, Json, ParseError, Player, Solution};
+use std::env;
+use std::fmt;
+use std::fs::{read_to_string, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Format {
+    // for humans
+    Text,
+    // one JSON object on stdout, everything else goes to stderr
+    Json,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Input {
+    fn read(&self) -> Result<String, String> {
+        match self {
+            Input::Stdin => {
+                let mut content = String::with_capacity(64 * 1024);
+                io::stdin()
+                    .read_to_string(&mut content)
+                    .map_err(|e| format!("unable to read stdin: {e}"))?;
+                Ok(content)
+            }
+            Input::File(path) => read_to_string(path)
+                .map_err(|e| format!("unable to read '{}': {e}", path.display())),
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::Stdin => write!(f, "-"),
+            Input::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Options {
+    // solved one after the other, stdin if no file is given
+    inputs: Vec<Input>,
+    format: Format,
+    // a file for the auxiliary output, instead of stdout (text) or stderr (JSON)
+    auxiliary_output: Option<PathBuf>,
+    // an image of the solution, only for a single input
+    export: Option<PathBuf>,
+    // play the animation of the day instead of printing the answers
+    animate: bool,
+    // for the parts running in parallel, one per core if not given
+    threads: Option<usize>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut inputs: Vec<Input> = Vec::with_capacity(args.len());
+    let mut format = Format::Text;
+    let mut auxiliary_output: Option<PathBuf> = None;
+    let mut export: Option<PathBuf> = None;
+    let mut animate = false;
+    let mut threads: Option<usize> = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match args.next().map(|s| s.as_str()) {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    Some(other) => {
+                        return Err(format!(
+                            "expected 'text' or 'json' after '--format', found '{other}'"
+                        ))
+                    }
+                    None => return Err("expected 'text' or 'json' after '--format'".to_owned()),
+                }
+            }
+            "--auxiliary-output" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "expected a file after '--auxiliary-output'".to_owned())?;
+                auxiliary_output = Some(PathBuf::from(path));
+            }
+            "--export" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "expected a file after '--export'".to_owned())?;
+                export = Some(PathBuf::from(path));
+            }
+            "--animate" => animate = true,
+            "--threads" => {
+                let n = args
+                    .next()
+                    .ok_or_else(|| "expected a number after '--threads'".to_owned())?;
+                threads =
+                    Some(n.parse().map_err(|_| {
+                        format!("expected a number after '--threads', found '{n}'")
+                    })?);
+            }
+            "-" => inputs.push(Input::Stdin),
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{arg}'")),
+            _ => inputs.push(Input::File(PathBuf::from(arg))),
+        }
+    }
+    if inputs.is_empty() {
+        inputs.push(Input::Stdin);
+    }
+    if export.is_some() && inputs.len() > 1 {
+        return Err("'--export' needs a single input, the image would be overwritten".to_owned());
+    }
+    Ok(Options {
+        inputs,
+        format,
+        auxiliary_output,
+        export,
+        animate,
+        threads,
+    })
+}
+
+// Everything a day produces for one input, together with how long it took.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Report {
+    pub day: u8,
+    pub part1: Result<Answer, String>,
+    pub part2: Result<Answer, String>,
+    pub auxiliary_output: Result<Option<String>, String>,
+    // parsing, part 1 and part 2
+    pub timings: [Duration; 3],
+}
+
+impl Report {
+    pub fn new<S: Solution>(content: &str) -> Result<Report, ParseError> {
+        let start = Instant::now();
+        let input = parse::<S>(content)?;
+        let parsed = Instant::now();
+        let part1 = S::part1(&input);
+        let solved1 = Instant::now();
+        let part2 = S::part2(&input);
+        let solved2 = Instant::now();
+        // not timed, it is not part of the solution
+        let auxiliary_output = S::auxiliary_output(&input);
+        Ok(Report {
+            day: S::DAY,
+            part1,
+            part2,
+            auxiliary_output,
+            timings: [parsed - start, solved1 - parsed, solved2 - solved1],
+        })
+    }
+
+    pub fn diagnostics(&self) -> Vec<String> {
+        let mut diagnostics: Vec<String> = Vec::with_capacity(3);
+        if let Err(e) = &self.part1 {
+            diagnostics.push(format!("part 1 failed: {e}"));
+        }
+        if let Err(e) = &self.part2 {
+            diagnostics.push(format!("part 2 failed: {e}"));
+        }
+        if let Err(e) = &self.auxiliary_output {
+            diagnostics.push(format!("auxiliary output failed: {e}"));
+        }
+        diagnostics
+    }
+
+    // Answers are numbers or strings, parts without an answer are null and the reason (if any)
+    // is in the diagnostics.
+    pub fn to_json(&self) -> Json {
+        let timings = Json::object(
+            ["parse_ns", "part1_ns", "part2_ns"]
+                .into_iter()
+                .zip(self.timings.iter().map(|t| Json::from(t.as_nanos()))),
+        );
+        record(
+            self.day,
+            [answer_json(&self.part1), answer_json(&self.part2)],
+            timings,
+            self.diagnostics(),
+        )
+    }
+}
+
+fn answer_json(answer: &Result<Answer, String>) -> Json {
+    match answer {
+        Ok(Answer::Number(n)) => Json::from(*n),
+        Ok(Answer::Text(s)) => Json::from(s.as_str()),
+        Ok(Answer::Unsolved) | Err(_) => Json::Null,
+    }
+}
+
+fn record(day: u8, [part1, part2]: [Json; 2], timings: Json, diagnostics: Vec<String>) -> Json {
+    Json::object([
+        ("day", Json::from(day)),
+        ("part1", part1),
+        ("part2", part2),
+        ("timings", timings),
+        (
+            "diagnostics",
+            Json::Array(diagnostics.into_iter().map(Json::from).collect()),
+        ),
+    ])
+}
+
+// a thin `main` for the binaries of the individual days
+pub fn main<S: Solution>() -> Result<(), String> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = parse_args(&args)?;
+    if let Some(threads) = options.threads {
+        set_threads(threads)?;
+    }
+    if options.animate {
+        return animate::<S>(&options.inputs);
+    }
+    let mut auxiliary_file = match &options.auxiliary_output {
+        Some(path) => Some(
+            File::create(path)
+                .map_err(|e| format!("unable to create '{}': {e}", path.display()))?,
+        ),
+        None => None,
+    };
+
+    let several = options.inputs.len() > 1;
+    let mut failed: Vec<String> = Vec::with_capacity(options.inputs.len());
+    for (i, input) in options.inputs.iter().enumerate() {
+        if several && options.format == Format::Text {
+            if i > 0 {
+                println!();
+            }
+            println!("==> {input} <==");
+        }
+        if let Err(e) = solve_input::<S>(input, &options, auxiliary_file.as_mut()) {
+            if !several {
+                return Err(e);
+            }
+            eprintln!("Error in {input}: {e}");
+            failed.push(input.to_string());
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("failed inputs: {}", failed.join(", ")))
+    }
+}
+
+fn animate<S: Solution>(inputs: &[Input]) -> Result<(), String> {
+    if inputs.contains(&Input::Stdin) {
+        return Err(
+            "an animation needs its input from a file, stdin is for the controls".to_owned(),
+        );
+    }
+    let controls = stdin_controls();
+    for input in inputs {
+        let content = input.read()?;
+        let input = parse::<S>(&content).map_err(|e| e.to_string())?;
+        let frames =
+            S::animation(&input).ok_or_else(|| format!("day {} has no animation", S::DAY))?;
+        Player::default()
+            .play(frames, &controls, &mut io::stdout().lock())
+            .map_err(|e| format!("unable to show the animation: {e}"))?;
+    }
+    Ok(())
+}
+
+fn solve_input<S: Solution>(
+    input: &Input,
+    options: &Options,
+    auxiliary_file: Option<&mut File>,
+) -> Result<(), String> {
+    let content = input.read();
+    let report = content
+        .as_ref()
+        .map_err(|e| e.clone())
+        .and_then(|content| Report::new::<S>(content).map_err(|e| e.to_string()));
+    let report = match report {
+        Ok(report) => report,
+        Err(e) => {
+            if options.format == Format::Json {
+                let record = record(
+                    S::DAY,
+                    [Json::Null, Json::Null],
+                    Json::Null,
+                    vec![e.clone()],
+                );
+                println!("{}", with_input(record, input));
+            }
+            return Err(e);
+        }
+    };
+
+    match options.format {
+        Format::Text => {
+            println!("Day {:02}", report.day);
+            print_part(1, &report.part1);
+            print_part(2, &report.part2);
+        }
+        // one line per input
+        Format::Json => println!("{}", with_input(report.to_json(), input)),
+    }
+
+    if let Ok(Some(output)) = &report.auxiliary_output {
+        match (auxiliary_file, options.format) {
+            (Some(file), _) => writeln!(file, "{output}")
+                .map_err(|e| format!("unable to write auxiliary output: {e}"))?,
+            (None, Format::Text) => {
+                println!();
+                println!("{output}");
+            }
+            // keep stdout parseable
+            (None, Format::Json) => eprintln!("{output}"),
+        }
+    }
+
+    report.auxiliary_output?;
+    if let (Some(path), Ok(content)) = (&options.export, &content) {
+        export::<S>(content, path)?;
+    }
+    report.part1.and(report.part2).map(|_| ())
+}
+
+fn export<S: Solution>(content: &str, path: &Path) -> Result<(), String> {
+    let input = parse::<S>(content).map_err(|e| e.to_string())?;
+    let picture =
+        S::picture(&input)?.ok_or_else(|| format!("day {} has no picture to export", S::DAY))?;
+    picture.save(path)
+}
+
+// adds the name of the input file right after the day
+fn with_input(record: Json, input: &Input) -> Json {
+    match record {
+        Json::Object(mut entries) => {
+            entries.insert(1, ("input".to_owned(), Json::from(input.to_string())));
+            Json::Object(entries)
+        }
+        other => other,
+    }
+}
+
+fn print_part(part: u8, answer: &Result<Answer, String>) {
+    match answer {
+        Ok(Answer::Unsolved) => (),
+        Ok(answer) => println!("Part {part}: {answer}"),
+        Err(e) => println!("Part {part} failed: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Count;
+
+    impl Solution for Count {
+        const DAY: u8 = 17;
+
+        type Input<'a> = Vec<&'a str>;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+            Ok(input.split(',').collect())
+        }
+
+        fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+            Ok(input.join("-").into())
+        }
+
+        fn part2(_input: &Self::Input<'_>) -> Result<Answer, String> {
+            Err("no solution for \"this\"".to_owned())
+        }
+    }
+
+    #[test]
+    fn parse_args_reads_format_and_auxiliary_output() {
+        // given
+        let args: Vec<String> = [
+            "in.txt",
+            "--format",
+            "json",
+            "--auxiliary-output",
+            "aux.txt",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        // when
+        let options = parse_args(&args);
+
+        // then
+        assert_eq!(
+            options,
+            Ok(Options {
+                inputs: vec![Input::File(PathBuf::from("in.txt"))],
+                format: Format::Json,
+                auxiliary_output: Some(PathBuf::from("aux.txt")),
+                export: None,
+                animate: false,
+                threads: None,
+            })
+        );
+        assert!(parse_args(&args[1..2]).is_err());
+    }
+
+    #[test]
+    fn parse_args_reads_stdin_and_several_files() {
+        // given
+        let args: Vec<String> = ["a.txt", "-", "b.txt"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        // when
+        let several = parse_args(&args).expect("expected args to parse");
+        let none = parse_args(&[]).expect("expected no args to parse");
+
+        // then
+        assert_eq!(
+            several.inputs,
+            vec![
+                Input::File(PathBuf::from("a.txt")),
+                Input::Stdin,
+                Input::File(PathBuf::from("b.txt")),
+            ]
+        );
+        assert_eq!(none.inputs, vec![Input::Stdin]);
+    }
+
+    #[test]
+    fn parse_args_allows_export_of_a_single_input() {
+        // given
+        let args: Vec<String> = ["a.txt", "--export", "a.png", "b.txt"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        // when
+        let single = parse_args(&args[..3]).expect("expected args to parse");
+
+        // then
+        assert_eq!(single.export, Some(PathBuf::from("a.png")));
+        assert!(parse_args(&args).is_err());
+    }
+
+    #[test]
+    fn parse_args_reads_threads() {
+        // given
+        let args: Vec<String> = ["--threads", "4", "a.txt", "--threads", "x"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        // when
+        let options = parse_args(&args[..3]).expect("expected args to parse");
+
+        // then
+        assert_eq!(options.threads, Some(4));
+        assert!(parse_args(&args[3..]).is_err());
+    }
+
+    #[test]
+    fn report_json_has_answers_and_diagnostics() {
+        // given
+        let report = Report::new::<Count>("2,4,1").expect("expected input to parse");
+
+        // when
+        let json = Json::parse(&report.to_json().to_string()).expect("expected valid JSON");
+
+        // then
+        assert_eq!(json.get("day"), Some(&Json::Integer(17)));
+        assert_eq!(json.get("part1"), Some(&Json::from("2-4-1")));
+        assert_eq!(json.get("part2"), Some(&Json::Null));
+        assert!(json
+            .get("timings")
+            .and_then(|t| t.get("part1_ns"))
+            .is_some());
+        assert_eq!(
+            json.get("diagnostics"),
+            Some(&Json::Array(vec![Json::from(
+                "part 2 failed: no solution for \"this\""
+            )]))
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::any::type_name;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+// An error in the puzzle input. Parsers create it from the part of the input that could not be
+// parsed (which has to be a slice of the original input), the location in the input (line and
+// column) is resolved later by `locate`, so the parsers don't need to keep track of line numbers.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub day: Option<u8>,
+    pub expected: String,
+    pub snippet: String,
+    pub location: Option<Location>,
+    // memory address range of the snippet, used to find the snippet in the original input
+    span: Range<usize>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Location {
+    // line and column start counting at 1, columns are counted in characters
+    pub line: usize,
+    pub column: usize,
+    pub source_line: String,
+}
+
+impl ParseError {
+    pub fn new(snippet: &str, expected: impl Into<String>) -> Self {
+        let start = snippet.as_ptr() as usize;
+        ParseError {
+            day: None,
+            expected: expected.into(),
+            snippet: snippet.to_owned(),
+            location: None,
+            span: start..start + snippet.len(),
+        }
+    }
+
+    // an error at the end of the given slice (e.g. for missing lines)
+    pub fn after(s: &str, expected: impl Into<String>) -> Self {
+        ParseError::new(&s[s.len()..], expected)
+    }
+
+    // an error for the character starting at byte index `index` of `s`
+    pub fn at_char(s: &str, index: usize, expected: impl Into<String>) -> Self {
+        let len = s[index..].chars().next().map(|c| c.len_utf8()).unwrap_or(0);
+        ParseError::new(&s[index..index + len], expected)
+    }
+
+    pub fn with_day(self, day: u8) -> Self {
+        ParseError {
+            day: Some(day),
+            ..self
+        }
+    }
+
+    // find the line and column of the snippet in the input. If the snippet is not part of the
+    // input, the error stays without location
+    pub fn locate(self, input: &str) -> Self {
+        if self.location.is_some() {
+            return self;
+        }
+        let input_start = input.as_ptr() as usize;
+        if self.span.start < input_start || self.span.end > input_start + input.len() {
+            return self;
+        }
+        let offset = self.span.start - input_start;
+        let line_start = input[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = input[offset..]
+            .find('\n')
+            .map(|i| offset + i)
+            .unwrap_or(input.len());
+        let location = Location {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            source_line: input[line_start..line_end].to_owned(),
+        };
+        ParseError {
+            location: Some(location),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        if let Some(location) = &self.location {
+            write!(f, "line {}, column {}: ", location.line, location.column)?;
+        }
+        write!(f, "expected {}, found ", self.expected)?;
+        let first_line = self.snippet.lines().next().unwrap_or("");
+        if first_line.is_empty() {
+            write!(f, "nothing")?;
+        } else if first_line.chars().count() > 40 || first_line.len() < self.snippet.len() {
+            let shortened: String = first_line.chars().take(40).collect();
+            write!(f, "'{shortened}…'")?;
+        } else {
+            write!(f, "'{first_line}'")?;
+        }
+
+        if let Some(location) = &self.location {
+            let line_number = location.line.to_string();
+            let padding = " ".repeat(line_number.len());
+            let underline_len = first_line
+                .chars()
+                .count()
+                .min(location.source_line.chars().count() + 1 - location.column)
+                .max(1);
+            writeln!(f)?;
+            writeln!(f, "{padding} |")?;
+            writeln!(f, "{line_number} | {}", location.source_line)?;
+            write!(
+                f,
+                "{padding} | {}{}",
+                " ".repeat(location.column - 1),
+                "^".repeat(underline_len)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn parse_number<T: FromStr>(s: &str) -> Result<T, ParseError> {
+    s.parse::<T>()
+        .map_err(|_| ParseError::new(s, format!("a number of type {}", type_name::<T>())))
+}
+
+pub fn expect_prefix<'a>(s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    s.strip_prefix(prefix).ok_or_else(|| {
+        let mut end = prefix.len().min(s.len());
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        ParseError::new(&s[..end], format!("'{prefix}'"))
+    })
+}
+
+pub fn expect_split<'a>(s: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(delimiter)
+        .ok_or_else(|| ParseError::new(s, format!("'{delimiter}'")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn locate_finds_line_and_column() {
+        // given
+        let input = "1 2\n3 x\n5 6\n";
+        let error = parse_number::<u32>(&input[6..7]).expect_err("expected parse error");
+
+        // when
+        let error = error.locate(input).with_day(1);
+
+        // then
+        let location = error
+            .location
+            .as_ref()
+            .expect("expected error to have a location");
+        assert_eq!(location.line, 2);
+        assert_eq!(location.column, 3);
+        assert_eq!(location.source_line, "3 x");
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 2, column 3: expected a number of type u32, found 'x'\n  |\n2 | 3 x\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn locate_ignores_snippets_outside_of_the_input() {
+        // given
+        let error = ParseError::new("foo", "bar");
+
+        // when
+        let error = error.locate("something else");
+
+        // then
+        assert_eq!(error.location, None);
+        assert_eq!(error.to_string(), "expected bar, found 'foo'");
+    }
+
+    #[test]
+    fn after_points_to_the_end_of_the_input() {
+        // given
+        let input = "Register A: 1\n";
+
+        // when
+        let error = ParseError::after(input, "another line").locate(input);
+
+        // then
+        let location = error
+            .location
+            .as_ref()
+            .expect("expected error to have a location");
+        assert_eq!(location.line, 2);
+        assert_eq!(location.column, 1);
+        assert_eq!(location.source_line, "");
+    }
+
+    #[test]
+    fn expect_prefix_points_at_the_mismatching_start() {
+        // given
+        let line = "Button C: X+1, Y+2";
+
+        // when
+        let error = expect_prefix(line, "Button A: ").expect_err("expected prefix to mismatch");
+
+        // then
+        assert_eq!(error.snippet, "Button C: ");
+        assert_eq!(error.expected, "'Button A: '");
+    }
+}
@@ -0,0 +1,395 @@
+use crate::{Cell, Color, Picture};
+use std::fmt::Write as _;
+use std::fs;
+use std::iter::repeat;
+use std::path::Path;
+
+// pixels per cell in PPM and PNG files, SVG files scale anyway
+const CELL_PIXELS: usize = 8;
+const SVG_CELL: usize = 12;
+
+// for cells without colours, like a terminal with a dark theme
+const BACKGROUND: Color = Color::BLACK;
+const FOREGROUND: Color = Color::WHITE;
+
+// Image files of pictures, written without the help of any external program or library.
+//
+// PPM and PNG cannot draw characters, so every symbol is drawn as a block in its colour on top of
+// the background of its cell. '.' is the empty tile of almost every puzzle map and becomes a small
+// dot. SVG files keep the symbols as text.
+impl Picture {
+    // PPM, PNG or SVG, depending on the extension of the file
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let content = match extension.to_ascii_lowercase().as_str() {
+            "ppm" => self.to_ppm(),
+            "png" => self.to_png(),
+            "svg" => self.to_svg().into_bytes(),
+            _ => {
+                return Err(format!(
+                    "unable to export '{}', expected a file ending in .ppm, .png or .svg",
+                    path.display()
+                ))
+            }
+        };
+        fs::write(path, content).map_err(|e| format!("unable to write '{}': {e}", path.display()))
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let (width, height, pixels) = self.pixels();
+        let mut out = format!("P6\n{width} {height}\n255\n").into_bytes();
+        out.extend_from_slice(&pixels);
+        out
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let (width, height, pixels) = self.pixels();
+        // Every row starts with the filter type. Filter 2 stores the difference to the row above,
+        // which is all zeros within a row of cells, and zeros compress well.
+        let mut raw: Vec<u8> = Vec::with_capacity(pixels.len() + height);
+        let mut above: &[u8] = &[];
+        for row in pixels.chunks(width * 3) {
+            raw.push(2);
+            raw.extend(
+                row.iter()
+                    .zip(above.iter().chain(repeat(&0)))
+                    .map(|(b, a)| b.wrapping_sub(*a)),
+            );
+            above = row;
+        }
+
+        let mut header: Vec<u8> = Vec::with_capacity(13);
+        header.extend_from_slice(&(width as u32).to_be_bytes());
+        header.extend_from_slice(&(height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, default compression, filtering and no interlacing
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut out: Vec<u8> = Vec::with_capacity(raw.len() / 8 + 64);
+        out.extend_from_slice(b"\x89PNG\r\n\x1a\n");
+        png_chunk(&mut out, b"IHDR", &header);
+        png_chunk(&mut out, b"IDAT", &zlib(&raw));
+        png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    pub fn to_svg(&self) -> String {
+        let (width, height) = (self.width() * SVG_CELL, self.height() * SVG_CELL);
+        let mut out = String::with_capacity(self.width() * self.height() * 64);
+        let _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        let _ = writeln!(
+            out,
+            r#"<rect width="{width}" height="{height}" fill="{}"/>"#,
+            hex(BACKGROUND)
+        );
+        for (y, row) in self.rows().enumerate() {
+            // one rectangle per run of cells with the same background
+            let mut x = 0;
+            while x < row.len() {
+                let run = row[x..].iter().take_while(|c| c.bg == row[x].bg).count();
+                if let Some(bg) = row[x].bg {
+                    let _ = writeln!(
+                        out,
+                        r#"<rect x="{}" y="{}" width="{}" height="{SVG_CELL}" fill="{}"/>"#,
+                        x * SVG_CELL,
+                        y * SVG_CELL,
+                        run * SVG_CELL,
+                        hex(bg)
+                    );
+                }
+                x += run;
+            }
+        }
+        let _ = writeln!(
+            out,
+            r#"<g font-family="monospace" font-size="{SVG_CELL}" text-anchor="middle" dominant-baseline="central">"#
+        );
+        for (y, row) in self.rows().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if cell.symbol.is_whitespace() {
+                    continue;
+                }
+                let _ = write!(
+                    out,
+                    r#"<text x="{}" y="{}" fill="{}">"#,
+                    x * SVG_CELL + SVG_CELL / 2,
+                    y * SVG_CELL + SVG_CELL / 2,
+                    hex(cell.fg.unwrap_or(FOREGROUND))
+                );
+                match cell.symbol {
+                    '<' => out.push_str("&lt;"),
+                    '>' => out.push_str("&gt;"),
+                    '&' => out.push_str("&amp;"),
+                    c => out.push(c),
+                }
+                out.push_str("</text>\n");
+            }
+        }
+        out.push_str("</g>\n</svg>\n");
+        out
+    }
+
+    // width, height and the RGB values of all pixels, row by row
+    fn pixels(&self) -> (usize, usize, Vec<u8>) {
+        let width = self.width() * CELL_PIXELS;
+        let height = self.height() * CELL_PIXELS;
+        let mut pixels: Vec<u8> = Vec::with_capacity(width * height * 3);
+        for row in self.rows() {
+            for py in 0..CELL_PIXELS {
+                for cell in row {
+                    for px in 0..CELL_PIXELS {
+                        let Color(r, g, b) = pixel(cell, px, py);
+                        pixels.extend_from_slice(&[r, g, b]);
+                    }
+                }
+            }
+        }
+        (width, height, pixels)
+    }
+}
+
+fn pixel(cell: &Cell, x: usize, y: usize) -> Color {
+    let background = cell.bg.unwrap_or(BACKGROUND);
+    let inside = |margin: usize| {
+        (margin..CELL_PIXELS - margin).contains(&x) && (margin..CELL_PIXELS - margin).contains(&y)
+    };
+    let symbol = match cell.symbol {
+        c if c.is_whitespace() => false,
+        '.' => inside(CELL_PIXELS / 2 - 1),
+        _ => inside(1),
+    };
+    if symbol {
+        cell.fg.unwrap_or(FOREGROUND)
+    } else {
+        background
+    }
+}
+
+fn hex(Color(r, g, b): Color) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(kind.iter().chain(data));
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    !bytes.into_iter().fold(!0u32, |crc, b| {
+        (0..8).fold(crc ^ *b as u32, |crc, _| {
+            (crc >> 1) ^ (0xedb88320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), byte| {
+        let a = (a + *byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+// A zlib stream with a single deflate block of fixed Huffman codes. The only compression is a
+// greedy search for repetitions, but pictures are mostly runs of the same colour, so that goes a
+// long way.
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut bits = BitWriter::default();
+    // final block, fixed Huffman codes
+    bits.write(1, 1);
+    bits.write(1, 2);
+
+    // the last position of every three byte sequence, by hash
+    let mut last: Vec<usize> = vec![usize::MAX; 1 << 15];
+    let hash = |i: usize| {
+        let key = (data[i] as usize) << 16 | (data[i + 1] as usize) << 8 | data[i + 2] as usize;
+        (key.wrapping_mul(2654435761) >> 7) & ((1 << 15) - 1)
+    };
+    let mut i = 0;
+    while i < data.len() {
+        let mut length = 0;
+        let mut distance = 0;
+        if i + 3 <= data.len() {
+            let h = hash(i);
+            let candidate = last[h];
+            last[h] = i;
+            if candidate != usize::MAX && i - candidate <= MAX_DISTANCE {
+                length = data[i..]
+                    .iter()
+                    .zip(&data[candidate..])
+                    .take(MAX_LENGTH)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                distance = i - candidate;
+            }
+        }
+        if length >= 3 {
+            bits.length(length);
+            bits.distance(distance);
+            for j in i + 1..(i + length).min(data.len().saturating_sub(2)) {
+                last[hash(j)] = j;
+            }
+            i += length;
+        } else {
+            bits.literal(data[i] as u16);
+            i += 1;
+        }
+    }
+    bits.literal(256);
+
+    let mut out: Vec<u8> = Vec::with_capacity(bits.out.len() + 6);
+    // deflate with a 32K window, no preset dictionary
+    out.extend_from_slice(&[0x78, 0x01]);
+    out.extend_from_slice(&bits.finish());
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+const MAX_LENGTH: usize = 258;
+const MAX_DISTANCE: usize = 32768;
+
+const LENGTH_BASES: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASES: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA_BITS: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+// Deflate writes values starting with the least significant bit, but Huffman codes starting with
+// the most significant one.
+#[derive(Default)]
+struct BitWriter {
+    out: Vec<u8>,
+    buffer: u64,
+    count: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u64, bits: u32) {
+        self.buffer |= value << self.count;
+        self.count += bits;
+        while self.count >= 8 {
+            self.out.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn code(&mut self, code: u64, bits: u32) {
+        self.write(code.reverse_bits() >> (64 - bits), bits);
+    }
+
+    // literal bytes, the end of the block (256) and lengths (257 and up)
+    fn literal(&mut self, value: u16) {
+        let value = value as u64;
+        match value {
+            0..=143 => self.code(0b00110000 + value, 8),
+            144..=255 => self.code(0b110010000 + value - 144, 9),
+            256..=279 => self.code(value - 256, 7),
+            _ => self.code(0b11000000 + value - 280, 8),
+        }
+    }
+
+    fn length(&mut self, length: usize) {
+        let index = LENGTH_BASES
+            .iter()
+            .rposition(|base| *base <= length)
+            .unwrap_or(0);
+        self.literal(257 + index as u16);
+        let extra = LENGTH_EXTRA_BITS[index];
+        self.write((length - LENGTH_BASES[index]) as u64, extra);
+    }
+
+    fn distance(&mut self, distance: usize) {
+        let index = DISTANCE_BASES
+            .iter()
+            .rposition(|base| *base <= distance)
+            .unwrap_or(0);
+        self.code(index as u64, 5);
+        let extra = DISTANCE_EXTRA_BITS[index];
+        self.write((distance - DISTANCE_BASES[index]) as u64, extra);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.out.push(self.buffer as u8);
+        }
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn picture() -> Picture {
+        let mut picture = Picture::from_fn(3, 2, |(x, _)| match x {
+            0 => Cell::new('#').fg(Color::GRAY),
+            _ => Cell::new('<'),
+        });
+        picture.highlight([(2, 1)], Color::RED);
+        picture
+    }
+
+    #[test]
+    fn ppm_has_a_block_per_symbol() {
+        // when
+        let ppm = picture().to_ppm();
+
+        // then
+        let header = b"P6\n24 16\n255\n";
+        assert!(ppm.starts_with(header));
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 24 * 16 * 3);
+        let at = |x: usize, y: usize| &pixels[(y * 24 + x) * 3..(y * 24 + x) * 3 + 3];
+        assert_eq!(at(0, 0), [0, 0, 0]);
+        assert_eq!(at(3, 3), [128, 128, 128]);
+        assert_eq!(at(16, 8), [220, 50, 47]);
+        assert_eq!(at(20, 12), [255, 255, 255]);
+    }
+
+    #[test]
+    fn png_chunks_have_valid_checksums() {
+        // when
+        let png = picture().to_png();
+
+        // then
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        let mut rest = &png[8..];
+        let mut kinds: Vec<&[u8]> = Vec::new();
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let crc = u32::from_be_bytes(rest[8 + len..12 + len].try_into().unwrap());
+            assert_eq!(crc32(&rest[4..8 + len]), crc);
+            kinds.push(&rest[4..8]);
+            rest = &rest[12 + len..];
+        }
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+        // the check value of the CRC-32 used by PNG
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+    }
+
+    #[test]
+    fn svg_escapes_symbols() {
+        // when
+        let svg = picture().to_svg();
+
+        // then
+        assert!(svg.contains(r##"<text x="18" y="6" fill="#ffffff">&lt;</text>"##));
+        assert!(svg.contains(r##"<rect x="24" y="12" width="12" height="12" fill="#dc322f"/>"##));
+    }
+}
@@ -0,0 +1,325 @@
+use crate::ParseError;
+use std::fmt;
+
+// Just enough JSON for the machine readable output of the runner and the binaries. Integers are
+// kept apart from floats, so large answers survive a round trip without losing precision.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    // keeps the order of the keys, so the output is stable
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<K: Into<String>>(entries: impl IntoIterator<Item = (K, Json)>) -> Json {
+        Json::Object(entries.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Json::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Integer(n) => Some(*n as f64),
+            Json::Float(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Json, ParseError> {
+        let (value, rest) = parse_value(input)?;
+        let rest = rest.trim_start();
+        if !rest.is_empty() {
+            return Err(ParseError::at_char(rest, 0, "the end of the input"));
+        }
+        Ok(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Self {
+        Json::String(s.to_owned())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Self {
+        Json::String(s)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Self {
+        Json::Bool(b)
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Json {
+                fn from(n: $t) -> Self {
+                    Json::Integer(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{b}"),
+            Json::Integer(n) => write!(f, "{n}"),
+            Json::Float(n) if n.is_finite() => write!(f, "{n:?}"),
+            Json::Float(_) => write!(f, "null"),
+            Json::String(s) => write_string(f, s),
+            Json::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+// parses one value from the start of `input` and returns the rest
+fn parse_value(input: &str) -> Result<(Json, &str), ParseError> {
+    let input = input.trim_start();
+    let expected = "a JSON value";
+    match input.chars().next() {
+        None => Err(ParseError::after(input, expected)),
+        Some('n') => literal(input, "null", Json::Null),
+        Some('t') => literal(input, "true", Json::Bool(true)),
+        Some('f') => literal(input, "false", Json::Bool(false)),
+        Some('"') => {
+            let (s, rest) = parse_string(input)?;
+            Ok((Json::String(s), rest))
+        }
+        Some('[') => {
+            let mut items: Vec<Json> = Vec::with_capacity(16);
+            let mut rest = input[1..].trim_start();
+            if let Some(r) = rest.strip_prefix(']') {
+                return Ok((Json::Array(items), r));
+            }
+            loop {
+                let (item, r) = parse_value(rest)?;
+                items.push(item);
+                rest = r.trim_start();
+                if let Some(r) = rest.strip_prefix(',') {
+                    rest = r;
+                } else if let Some(r) = rest.strip_prefix(']') {
+                    return Ok((Json::Array(items), r));
+                } else {
+                    return Err(error_at_start(rest, "',' or ']'"));
+                }
+            }
+        }
+        Some('{') => {
+            let mut entries: Vec<(String, Json)> = Vec::with_capacity(16);
+            let mut rest = input[1..].trim_start();
+            if let Some(r) = rest.strip_prefix('}') {
+                return Ok((Json::Object(entries), r));
+            }
+            loop {
+                rest = rest.trim_start();
+                if !rest.starts_with('"') {
+                    return Err(error_at_start(rest, "a string as object key"));
+                }
+                let (key, r) = parse_string(rest)?;
+                let r = r.trim_start();
+                let r = r
+                    .strip_prefix(':')
+                    .ok_or_else(|| error_at_start(r, "':'"))?;
+                let (value, r) = parse_value(r)?;
+                entries.push((key, value));
+                rest = r.trim_start();
+                if let Some(r) = rest.strip_prefix(',') {
+                    rest = r;
+                } else if let Some(r) = rest.strip_prefix('}') {
+                    return Ok((Json::Object(entries), r));
+                } else {
+                    return Err(error_at_start(rest, "',' or '}'"));
+                }
+            }
+        }
+        Some(c) if c == '-' || c.is_ascii_digit() => {
+            let end = input
+                .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+                .unwrap_or(input.len());
+            let (number, rest) = input.split_at(end);
+            if let Ok(n) = number.parse::<i128>() {
+                Ok((Json::Integer(n), rest))
+            } else if let Ok(n) = number.parse::<f64>() {
+                Ok((Json::Float(n), rest))
+            } else {
+                Err(ParseError::new(number, "a number"))
+            }
+        }
+        Some(_) => Err(ParseError::at_char(input, 0, expected)),
+    }
+}
+
+fn error_at_start(s: &str, expected: &str) -> ParseError {
+    if s.is_empty() {
+        ParseError::after(s, expected)
+    } else {
+        ParseError::at_char(s, 0, expected)
+    }
+}
+
+fn literal<'a>(input: &'a str, word: &str, value: Json) -> Result<(Json, &'a str), ParseError> {
+    match input.strip_prefix(word) {
+        Some(rest) => Ok((value, rest)),
+        None => Err(ParseError::at_char(input, 0, format!("'{word}'"))),
+    }
+}
+
+// `input` starts with the opening quote
+fn parse_string(input: &str) -> Result<(String, &str), ParseError> {
+    let mut s = String::with_capacity(32);
+    let mut chars = input.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((s, &input[i + 1..])),
+            '\\' => {
+                let (j, escaped) = chars
+                    .next()
+                    .ok_or_else(|| ParseError::after(input, "an escaped character"))?;
+                match escaped {
+                    '"' | '\\' | '/' => s.push(escaped),
+                    'n' => s.push('\n'),
+                    'r' => s.push('\r'),
+                    't' => s.push('\t'),
+                    'b' => s.push('\u{8}'),
+                    'f' => s.push('\u{c}'),
+                    'u' => {
+                        let hex = input
+                            .get(j + 1..j + 5)
+                            .ok_or_else(|| ParseError::after(input, "four hex digits"))?;
+                        let c = u32::from_str_radix(hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| ParseError::new(hex, "four hex digits"))?;
+                        s.push(c);
+                        for _ in 0..4 {
+                            chars.next();
+                        }
+                    }
+                    _ => return Err(ParseError::at_char(input, j, "an escape sequence")),
+                }
+            }
+            c => s.push(c),
+        }
+    }
+    Err(ParseError::after(input, "a closing '\"'"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn json_round_trips() {
+        // given
+        let value = Json::object([
+            ("day", Json::from(17u8)),
+            ("answer", Json::from("2,7,4,\"x\"\n")),
+            ("big", Json::from(u64::MAX)),
+            ("time", Json::Float(0.25)),
+            ("parts", Json::Array(vec![Json::Null, Json::Bool(true)])),
+        ]);
+
+        // when
+        let text = value.to_string();
+        let parsed = Json::parse(&text);
+
+        // then
+        assert_eq!(
+            text,
+            r#"{"day":17,"answer":"2,7,4,\"x\"\n","big":18446744073709551615,"time":0.25,"parts":[null,true]}"#
+        );
+        assert_eq!(parsed, Ok(value));
+    }
+
+    #[test]
+    fn parse_reports_location_of_errors() {
+        // given
+        let input = "{\n  \"day\": 1,\n  \"part1\" 2\n}";
+
+        // when
+        let error = Json::parse(input)
+            .expect_err("expected missing colon to be rejected")
+            .locate(input);
+
+        // then
+        let location = error.location.expect("expected error to have a location");
+        assert_eq!((location.line, location.column), (3, 11));
+        assert_eq!(error.expected, "':'");
+    }
+}
@@ -0,0 +1,21 @@
+#![forbid(unsafe_code)]
+
+mod animation;
+mod answer;
+mod cli;
+mod error;
+mod image;
+mod json;
+mod parallel;
+mod picture;
+pub mod random;
+mod solution;
+
+pub use animation::{stdin_controls, Animation, Command, Frame, Player};
+pub use answer::Answer;
+pub use cli::{main, Format, Report};
+pub use error::{expect_prefix, expect_split, parse_number, Location, ParseError};
+pub use json::Json;
+pub use parallel::{par_map, set_threads, threads};
+pub use picture::{Cell, Color, Picture};
+pub use solution::{parse, solve, Answers, Solution};
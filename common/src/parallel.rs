@@ -0,0 +1,105 @@
+use std::num::NonZeroUsize;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+// 0 stands for one thread per core
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+// chunks per thread, more of them balance items that take very different times
+const CHUNKS_PER_THREAD: usize = 8;
+
+// Sets the number of threads for `par_map`, e.g. from `--threads`. Without the `parallel` feature
+// everything runs on the calling thread, so only a single thread is accepted then.
+pub fn set_threads(threads: usize) -> Result<(), String> {
+    if threads == 0 {
+        return Err("expected at least one thread".to_owned());
+    }
+    if threads > 1 && !cfg!(feature = "parallel") {
+        return Err(format!(
+            "running on {threads} threads needs the 'parallel' feature"
+        ));
+    }
+    THREADS.store(threads, Ordering::Relaxed);
+    Ok(())
+}
+
+pub fn threads() -> usize {
+    if !cfg!(feature = "parallel") {
+        return 1;
+    }
+    match THREADS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        threads => threads,
+    }
+}
+
+// Applies `f` to all items on up to `threads()` scoped threads. The results are in the order of
+// the items, so answers do not depend on the number of threads.
+pub fn par_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    map_on(threads(), items, f)
+}
+
+fn map_on<T: Sync, R: Send>(threads: usize, items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = threads.min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let chunk_len = items.len().div_ceil(threads * CHUNKS_PER_THREAD);
+    let chunks: Vec<&[T]> = items.chunks(chunk_len).collect();
+    // the threads take the next chunk whenever they are done with one
+    let next_chunk = AtomicUsize::new(0);
+    let results: Mutex<Vec<(usize, Vec<R>)>> = Mutex::new(Vec::with_capacity(chunks.len()));
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| loop {
+                    let i = next_chunk.fetch_add(1, Ordering::Relaxed);
+                    let Some(chunk) = chunks.get(i) else {
+                        break;
+                    };
+                    let mapped: Vec<R> = chunk.iter().map(&f).collect();
+                    results
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .push((i, mapped));
+                })
+            })
+            .collect();
+        for worker in workers {
+            if let Err(payload) = worker.join() {
+                panic::resume_unwind(payload);
+            }
+        }
+    });
+
+    let mut results = results
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    results.sort_unstable_by_key(|(i, _)| *i);
+    results.into_iter().flat_map(|(_, mapped)| mapped).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn map_on_keeps_the_order_of_the_items() {
+        // given
+        let items: Vec<u64> = (0..1000).collect();
+
+        // when
+        let mapped = map_on(4, &items, |i| i * i);
+
+        // then
+        assert_eq!(mapped, items.iter().map(|i| i * i).collect::<Vec<u64>>());
+        assert_eq!(map_on(4, &[] as &[u64], |i| *i), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn set_threads_rejects_zero() {
+        assert!(set_threads(0).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use std::fmt::{self, Write};
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const WHITE: Color = Color(255, 255, 255);
+    pub const GRAY: Color = Color(128, 128, 128);
+    pub const DARK_GRAY: Color = Color(64, 64, 64);
+    pub const RED: Color = Color(220, 50, 47);
+    pub const GREEN: Color = Color(80, 200, 80);
+    pub const BLUE: Color = Color(38, 139, 210);
+    pub const YELLOW: Color = Color(230, 200, 40);
+    pub const ORANGE: Color = Color(230, 130, 30);
+    pub const CYAN: Color = Color(42, 180, 170);
+    pub const MAGENTA: Color = Color(200, 60, 140);
+}
+
+// One character of a picture. Without colours it is drawn in the default colours of the terminal.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Cell {
+    pub symbol: char,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl Cell {
+    pub const fn new(symbol: char) -> Self {
+        Cell {
+            symbol,
+            fg: None,
+            bg: None,
+        }
+    }
+
+    pub const fn fg(self, color: Color) -> Self {
+        Cell {
+            fg: Some(color),
+            ..self
+        }
+    }
+
+    pub const fn bg(self, color: Color) -> Self {
+        Cell {
+            bg: Some(color),
+            ..self
+        }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell::new(' ')
+    }
+}
+
+// A grid of coloured characters, the common ground for drawing the state of any day. Positions
+// are (x, y) like in the `grid` crate, positions outside of the picture are ignored when drawing.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Picture {
+    width: usize,
+    height: usize,
+    cells: Box<[Cell]>,
+}
+
+impl Picture {
+    pub fn new(width: usize, height: usize) -> Self {
+        Picture {
+            width,
+            height,
+            cells: vec![Cell::default(); width * height].into_boxed_slice(),
+        }
+    }
+
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut cell: impl FnMut((usize, usize)) -> Cell,
+    ) -> Self {
+        let mut cells: Vec<Cell> = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(cell((x, y)));
+            }
+        }
+        Picture {
+            width,
+            height,
+            cells: cells.into_boxed_slice(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&Cell> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn set(&mut self, (x, y): (usize, usize), cell: Cell) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = cell;
+        }
+    }
+
+    // Colours the background behind the given positions, e.g. to mark a path or visited cells.
+    pub fn highlight(&mut self, positions: impl IntoIterator<Item = (usize, usize)>, color: Color) {
+        for (x, y) in positions {
+            if x < self.width && y < self.height {
+                self.cells[y * self.width + x].bg = Some(color);
+            }
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        // `max(1)`, because `chunks` does not like a width of 0
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    // The picture with ANSI escape codes for 24 bit colours, as understood by most terminals.
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::with_capacity(self.cells.len() * 4 + self.height);
+        for row in self.rows() {
+            let mut style: (Option<Color>, Option<Color>) = (None, None);
+            for cell in row {
+                if (cell.fg, cell.bg) != style {
+                    out.push_str("\x1b[0m");
+                    if let Some(Color(r, g, b)) = cell.fg {
+                        let _ = write!(out, "\x1b[38;2;{r};{g};{b}m");
+                    }
+                    if let Some(Color(r, g, b)) = cell.bg {
+                        let _ = write!(out, "\x1b[48;2;{r};{g};{b}m");
+                    }
+                    style = (cell.fg, cell.bg);
+                }
+                out.push(cell.symbol);
+            }
+            if style != (None, None) {
+                out.push_str("\x1b[0m");
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+// only the symbols, without any colours
+impl fmt::Display for Picture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                f.write_char(cell.symbol)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn picture_renders_colours_only_where_they_change() {
+        // given
+        let mut picture = Picture::from_fn(3, 2, |(x, _)| {
+            if x == 0 {
+                Cell::new('#').fg(Color::GRAY)
+            } else {
+                Cell::new('.')
+            }
+        });
+
+        // when
+        picture.highlight([(1, 1), (2, 1), (7, 7)], Color::RED);
+
+        // then
+        assert_eq!(picture.to_string(), "#..\n#..\n");
+        assert_eq!(
+            picture.to_ansi(),
+            "\x1b[0m\x1b[38;2;128;128;128m#\x1b[0m..\n\
+             \x1b[0m\x1b[38;2;128;128;128m#\x1b[0m\x1b[48;2;220;50;47m..\x1b[0m\n"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use std::ops::Range;
+
+// A small deterministic pseudo random number generator (SplitMix64). Good enough for generating
+// test inputs, the same seed always gives the same numbers on every platform.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // uniform in `0..n`, `n` must not be 0
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "empty range {range:?}");
+        let len = range.end.abs_diff(range.start);
+        range.start.wrapping_add(self.below(len) as i64)
+    }
+
+    pub fn range_usize(&mut self, range: Range<usize>) -> usize {
+        assert!(range.start < range.end, "empty range {range:?}");
+        range.start + self.below((range.end - range.start) as u64) as usize
+    }
+
+    // true with a probability of `numerator / denominator`
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range_usize(0..items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range_usize(0..i + 1));
+        }
+    }
+}
+
+// What `aoc gen` passes to the generators, days ignore the options that do not apply to them.
+// `size` is the main dimension of the input (lines, width, ...), without it the input is about as
+// large as a real one.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct GenOptions {
+    pub size: Option<usize>,
+    // pairs of gates with swapped outputs (day 24)
+    pub swaps: Option<usize>,
+}
+
+// Checks a property for `cases` randomly generated inputs. Each case gets its own seed, which is
+// part of the panic message if the property does not hold, so failures can be reproduced with
+// `Rng::new(seed)`.
+pub fn check_property(cases: u64, mut property: impl FnMut(&mut Rng) -> Result<(), String>) {
+    for seed in 0..cases {
+        let mut rng = Rng::new(seed);
+        if let Err(e) = property(&mut rng) {
+            panic!("property does not hold for seed {seed}: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rng_is_deterministic_and_stays_in_range() {
+        // given
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+
+        // then
+        for _ in 0..1000 {
+            assert_eq!(a.next_u64(), b.next_u64());
+            let n = a.range(-3..4);
+            assert!((-3..4).contains(&n));
+            assert!(b.range_usize(10..12) >= 10);
+        }
+        let mut items = [1, 2, 3, 4, 5];
+        a.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+}
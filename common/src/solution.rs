@@ -0,0 +1,113 @@
+use crate::random::{GenOptions, Rng};
+use crate::{Animation, Answer, ParseError, Picture};
+
+// The interface every day implements. Parsing is separated from the two parts, so the parsed
+// input can be shared by both parts (and be reused by anyone who wants to embed a solution).
+pub trait Solution {
+    const DAY: u8;
+
+    type Input<'a>;
+
+    // Errors returned here do not need to carry the day or location, `common::parse` adds them.
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String>;
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String>;
+
+    // Output that is not an answer, but may help to interpret it (e.g. a rendered image to
+    // double-check an answer). Most days do not have anything like that.
+    fn auxiliary_output(_input: &Self::Input<'_>) -> Result<Option<String>, String> {
+        Ok(None)
+    }
+
+    // The simulation behind the solution as frames that can be watched with `--animate`, for
+    // days where seeing it helps to understand the puzzle.
+    fn animation<'a>(_input: &'a Self::Input<'_>) -> Option<Animation<'a>> {
+        None
+    }
+
+    // A picture of the solution for `--export`, by default the last frame of the animation.
+    fn picture(input: &Self::Input<'_>) -> Result<Option<Picture>, String> {
+        Ok(Self::animation(input)
+            .and_then(|frames| frames.last())
+            .map(|frame| frame.picture))
+    }
+
+    // A random input in the format of the puzzle, for `aoc gen`. The same random numbers and
+    // options give the same input.
+    fn generate(_rng: &mut Rng, _options: &GenOptions) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Answers {
+    pub part1: Result<Answer, String>,
+    pub part2: Result<Answer, String>,
+}
+
+pub fn parse<S: Solution>(content: &str) -> Result<S::Input<'_>, ParseError> {
+    S::parse(content).map_err(|e| e.locate(content).with_day(S::DAY))
+}
+
+pub fn solve<S: Solution>(content: &str) -> Result<Answers, ParseError> {
+    let input = parse::<S>(content)?;
+    Ok(Answers {
+        part1: S::part1(&input),
+        part2: S::part2(&input),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 1;
+
+        type Input<'a> = Vec<&'a str>;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+            if input.is_empty() {
+                return Err(ParseError::after(input, "at least one number"));
+            }
+            Ok(input.split_whitespace().collect())
+        }
+
+        fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+            input
+                .iter()
+                .map(|n| n.parse::<i64>().map_err(|e| e.to_string()))
+                .sum::<Result<i64, String>>()
+                .map(Answer::from)
+        }
+
+        fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+            Ok(input.concat().into())
+        }
+    }
+
+    #[test]
+    fn solve_keeps_errors_of_single_parts() {
+        // when
+        let answers = solve::<Sum>("1 a 3");
+
+        // then
+        let answers = answers.expect("expected input to parse");
+        assert!(answers.part1.is_err());
+        assert_eq!(answers.part2, Ok(Answer::Text("1a3".to_owned())));
+    }
+
+    #[test]
+    fn parse_adds_day_and_location_to_errors() {
+        // when
+        let error = parse::<Sum>("").expect_err("expected empty input to fail");
+
+        // then
+        assert_eq!(error.day, Some(1));
+        assert!(error.location.is_some());
+    }
+}
@@ -0,0 +1,101 @@
+use common::random::{GenOptions, Rng};
+use common::{parse_number, Answer, ParseError, Solution};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+pub mod reconcile;
+pub mod streaming;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input<'a> = Box<[(u32, u32)]>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(difference_sum(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(similarity_score(input).into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(1000)))
+    }
+}
+
+pub fn difference_sum(ids: &[(u32, u32)]) -> u32 {
+    let (mut left, mut right): (Vec<u32>, Vec<u32>) = ids.iter().copied().unzip();
+
+    left.sort_unstable();
+    right.sort_unstable();
+
+    left.iter()
+        .zip(right)
+        .map(|(l, r)| l.max(&r) - l.min(&r))
+        .sum()
+}
+
+pub fn similarity_score(ids: &[(u32, u32)]) -> u32 {
+    let mut counter_right: HashMap<u32, u32> = HashMap::with_capacity(ids.len());
+
+    for (_, right) in ids {
+        counter_right
+            .entry(*right)
+            .and_modify(|c| *c += 1)
+            .or_insert(1);
+    }
+
+    ids.iter()
+        .map(|(left, _)| counter_right.get(left).copied().unwrap_or(0) * left)
+        .sum()
+}
+
+pub fn parse(content: &str) -> Result<Box<[(u32, u32)]>, ParseError> {
+    content.lines().map(parse_line).collect()
+}
+
+fn parse_line(line: &str) -> Result<(u32, u32), ParseError> {
+    match fields(line)[..] {
+        [left, right] => Ok((parse_number(left)?, parse_number(right)?)),
+        _ => Err(ParseError::new(
+            line,
+            "two location IDs separated by whitespace, ',' or a tab",
+        )),
+    }
+}
+
+// the values of a line of CSV, TSV or separated by any whitespace
+fn fields(line: &str) -> Vec<&str> {
+    if line.contains(',') {
+        line.split(',').map(str::trim).collect()
+    } else if line.contains('\t') {
+        line.split('\t').map(str::trim).collect()
+    } else {
+        line.split_whitespace().collect()
+    }
+}
+
+// Two columns of five digit location ids. Some ids of the left list appear in the right one
+// (possibly several times), so the similarity score is not always 0.
+pub fn generate(rng: &mut Rng, lines: usize) -> String {
+    let left: Box<[u32]> = (0..lines)
+        .map(|_| rng.range(10000..100000) as u32)
+        .collect();
+    let mut input = String::with_capacity(lines * 14);
+    for l in left.iter() {
+        let r = if rng.chance(1, 3) {
+            *rng.choose(&left)
+        } else {
+            rng.range(10000..100000) as u32
+        };
+        let _ = writeln!(input, "{l}   {r}");
+    }
+    input
+}
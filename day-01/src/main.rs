@@ -1,64 +1,124 @@
-use std::collections::HashMap;
+use day_01::reconcile::{parse_columns, reconcile as reconcile_columns, report, Metric, Pairing};
+use day_01::streaming::{reconcile, StreamOptions};
 use std::env;
-use std::fs::read_to_string;
-use std::path::Path;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::PathBuf;
 
 fn main() -> Result<(), String> {
-    let filename = env::args()
-        .nth(1)
-        .ok_or_else(|| "No file name given.".to_owned())?;
-    let content = read_to_string(Path::new(&filename)).map_err(|e| e.to_string())?;
-
-    let ids = parse(&content)?;
-
-    println!("The sum of differences is: {}", difference_sum(&ids));
-    println!("The similarity score is: {}", similarity_score(&ids));
-
-    Ok(())
-}
-
-fn difference_sum(ids: &[(u32, u32)]) -> u32 {
-    let (mut left, mut right): (Vec<u32>, Vec<u32>) = ids.iter().copied().unzip();
-
-    left.sort_unstable();
-    right.sort_unstable();
-
-    left.iter()
-        .zip(right)
-        .map(|(l, r)| l.max(&r) - l.min(&r))
-        .sum()
+    let args: Vec<String> = env::args().skip(1).collect();
+    // lists larger than the memory need a mode of their own, the common interface reads the
+    // whole input at once
+    if args.first().is_some_and(|arg| arg == "--stream") {
+        return stream(&args[1..]);
+    }
+    if args.first().is_some_and(|arg| arg == "--reconcile") {
+        return reconcile_lists(&args[1..]);
+    }
+    common::main::<day_01::Day01>()
 }
 
-fn similarity_score(ids: &[(u32, u32)]) -> u32 {
-    let mut counter_right: HashMap<u32, u32> = HashMap::with_capacity(ids.len());
-
-    for (_, right) in ids {
-        counter_right
-            .entry(*right)
-            .and_modify(|c| *c += 1)
-            .or_insert(1);
+fn stream(args: &[String]) -> Result<(), String> {
+    let mut options = StreamOptions::default();
+    let mut input: Option<PathBuf> = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--chunk-lines" => {
+                let n = args
+                    .next()
+                    .ok_or_else(|| "expected a number after '--chunk-lines'".to_owned())?;
+                options.chunk_lines = n.parse().ok().filter(|n| *n > 0).ok_or_else(|| {
+                    format!("expected a positive number of lines per chunk, found '{n}'")
+                })?;
+            }
+            "--temp-dir" => {
+                let dir = args
+                    .next()
+                    .ok_or_else(|| "expected a directory after '--temp-dir'".to_owned())?;
+                options.temp_dir = PathBuf::from(dir);
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{arg}'")),
+            _ if input.is_none() => input = Some(PathBuf::from(arg)),
+            _ => {
+                return Err(format!(
+                    "unexpected argument '{arg}', streaming reads one input"
+                ))
+            }
+        }
     }
 
-    ids.iter()
-        .map(|(left, _)| counter_right.get(left).copied().unwrap_or(0) * left)
-        .sum()
+    let totals = match input.filter(|path| path.as_os_str() != "-") {
+        Some(path) => {
+            let file = File::open(&path)
+                .map_err(|e| format!("unable to read '{}': {e}", path.display()))?;
+            reconcile(BufReader::with_capacity(1 << 20, file), &options)?
+        }
+        None => reconcile(io::stdin().lock(), &options)?,
+    };
+    println!("Part 1: {}", totals.difference_sum);
+    println!("Part 2: {}", totals.similarity_score);
+    Ok(())
 }
 
-fn parse(content: &str) -> Result<Box<[(u32, u32)]>, String> {
-    content.lines().map(parse_line).collect()
-}
+fn reconcile_lists(args: &[String]) -> Result<(), String> {
+    let mut metric = Metric::Absolute;
+    let mut pairing = Pairing::Pairwise;
+    let mut worst = 0;
+    let mut input: Option<PathBuf> = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--metric" => {
+                let name = args
+                    .next()
+                    .ok_or_else(|| "expected a metric after '--metric'".to_owned())?;
+                metric = Metric::parse(name).ok_or_else(|| {
+                    format!(
+                        "unknown metric '{name}', expected absolute, squared, rank or frequency"
+                    )
+                })?;
+            }
+            "--reference" => {
+                let n = args
+                    .next()
+                    .ok_or_else(|| "expected a column after '--reference'".to_owned())?;
+                let column: usize = n.parse().ok().filter(|n| *n > 0).ok_or_else(|| {
+                    format!("expected a column number starting at 1, found '{n}'")
+                })?;
+                pairing = Pairing::Reference(column - 1);
+            }
+            "--worst" => {
+                let n = args
+                    .next()
+                    .ok_or_else(|| "expected a number after '--worst'".to_owned())?;
+                worst = n
+                    .parse()
+                    .map_err(|_| format!("expected a number of pairs, found '{n}'"))?;
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{arg}'")),
+            _ if input.is_none() => input = Some(PathBuf::from(arg)),
+            _ => {
+                return Err(format!(
+                    "unexpected argument '{arg}', reconciliation reads one input"
+                ))
+            }
+        }
+    }
 
-fn parse_line(line: &str) -> Result<(u32, u32), String> {
-    let (left, right) = line
-        .split_once(" ")
-        .ok_or_else(|| format!("no whitespace in line '{line}'"))?;
-    let left = left
-        .trim()
-        .parse::<u32>()
-        .map_err(|e| format!("unable to parse left part of line '{line}': {e}"))?;
-    let right = right
-        .trim()
-        .parse::<u32>()
-        .map_err(|e| format!("unable to parse right part of line '{line}': {e}"))?;
-    Ok((left, right))
+    let text = match input.filter(|path| path.as_os_str() != "-") {
+        Some(path) => fs::read_to_string(&path)
+            .map_err(|e| format!("unable to read '{}': {e}", path.display()))?,
+        None => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("unable to read the input: {e}"))?;
+            text
+        }
+    };
+    let columns = parse_columns(&text).map_err(|e| e.locate(&text).with_day(1).to_string())?;
+    let scores = reconcile_columns(&columns, pairing, metric)?;
+    print!("{}", report(&scores, metric, worst));
+    Ok(())
 }
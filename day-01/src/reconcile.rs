@@ -0,0 +1,353 @@
+use crate::fields;
+use common::{parse_number, ParseError};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+// The puzzle for any number of lists: one column per list, signed 64 bit values.
+pub type Columns = Box<[Box<[i64]>]>;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Metric {
+    // the sum of the differences between the sorted columns (part 1)
+    Absolute,
+    // like `Absolute`, but a few large differences count more than many small ones
+    Squared,
+    // how far apart the ranks of the two values on each line are (Spearman's footrule), so only
+    // the order of the values counts, not their size
+    Rank,
+    // every value times how often it appears in the other column (part 2). Unlike the other
+    // metrics this is a similarity, higher is better.
+    FrequencyWeighted,
+}
+
+impl Metric {
+    pub fn parse(name: &str) -> Option<Metric> {
+        match name {
+            "absolute" => Some(Metric::Absolute),
+            "squared" => Some(Metric::Squared),
+            "rank" => Some(Metric::Rank),
+            "frequency" => Some(Metric::FrequencyWeighted),
+            _ => None,
+        }
+    }
+
+    pub fn is_similarity(self) -> bool {
+        self == Metric::FrequencyWeighted
+    }
+
+    // Which value of `right` each value of `left` is compared with, and what that costs. The
+    // score of two columns is the sum of the costs. Squares of differences close to the range of
+    // `i64` do not fit into the `i128` of the cost.
+    pub fn pairs(self, left: &[i64], right: &[i64]) -> Result<Vec<Pair>, String> {
+        let pairs = match self {
+            Metric::Absolute | Metric::Squared => {
+                let (mut left, mut right) = (left.to_vec(), right.to_vec());
+                left.sort_unstable();
+                right.sort_unstable();
+                left.into_iter()
+                    .zip(right)
+                    .map(|(l, r)| {
+                        let difference = l.abs_diff(r) as i128;
+                        let cost = if self == Metric::Squared {
+                            difference.checked_mul(difference).ok_or_else(|| {
+                                format!("the squared difference of {l} and {r} is too large")
+                            })?
+                        } else {
+                            difference
+                        };
+                        Ok(Pair {
+                            left: l,
+                            right: r,
+                            cost,
+                        })
+                    })
+                    .collect::<Result<Vec<Pair>, String>>()?
+            }
+            Metric::Rank => {
+                let (left_ranks, right_ranks) = (ranks(left), ranks(right));
+                (0..left.len().min(right.len()))
+                    .map(|i| Pair {
+                        left: left[i],
+                        right: right[i],
+                        cost: left_ranks[i].abs_diff(right_ranks[i]) as i128,
+                    })
+                    .collect()
+            }
+            Metric::FrequencyWeighted => {
+                let mut counter_right: HashMap<i64, i128> = HashMap::with_capacity(right.len());
+                for r in right {
+                    *counter_right.entry(*r).or_insert(0) += 1;
+                }
+                left.iter()
+                    .map(|l| Pair {
+                        left: *l,
+                        right: *l,
+                        cost: *l as i128 * counter_right.get(l).copied().unwrap_or(0),
+                    })
+                    .collect()
+            }
+        };
+        Ok(pairs)
+    }
+}
+
+impl std::fmt::Display for Metric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Metric::Absolute => "absolute",
+            Metric::Squared => "squared",
+            Metric::Rank => "rank",
+            Metric::FrequencyWeighted => "frequency",
+        };
+        f.write_str(name)
+    }
+}
+
+// position of every value in the sorted column, equal values are ranked in the order of their
+// lines
+fn ranks(column: &[i64]) -> Box<[usize]> {
+    let mut order: Box<[usize]> = (0..column.len()).collect();
+    order.sort_by_key(|i| column[*i]);
+    let mut ranks: Box<[usize]> = vec![0; column.len()].into_boxed_slice();
+    for (rank, i) in order.iter().enumerate() {
+        ranks[*i] = rank;
+    }
+    ranks
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Pair {
+    pub left: i64,
+    pub right: i64,
+    pub cost: i128,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Pairing {
+    // every column with every later one
+    Pairwise,
+    // every other column with the column of this index
+    Reference(usize),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ColumnScore {
+    // indices of the compared columns
+    pub left: usize,
+    pub right: usize,
+    pub score: i128,
+    pub pairs: Vec<Pair>,
+}
+
+impl ColumnScore {
+    // the `n` pairs that contribute the most to the distance (or the least to the similarity)
+    pub fn worst_pairs(&self, metric: Metric, n: usize) -> Vec<Pair> {
+        let mut pairs = self.pairs.clone();
+        if metric.is_similarity() {
+            pairs.sort_by_key(|pair| pair.cost);
+        } else {
+            pairs.sort_by_key(|pair| std::cmp::Reverse(pair.cost));
+        }
+        pairs.truncate(n);
+        pairs
+    }
+}
+
+pub fn reconcile(
+    columns: &[Box<[i64]>],
+    pairing: Pairing,
+    metric: Metric,
+) -> Result<Vec<ColumnScore>, String> {
+    let column_pairs: Vec<(usize, usize)> = match pairing {
+        Pairing::Pairwise => (0..columns.len())
+            .flat_map(|l| (l + 1..columns.len()).map(move |r| (l, r)))
+            .collect(),
+        Pairing::Reference(reference) if reference < columns.len() => (0..columns.len())
+            .filter(|c| *c != reference)
+            .map(|c| (c, reference))
+            .collect(),
+        Pairing::Reference(reference) => {
+            return Err(format!(
+                "there is no reference column {}, only {} columns",
+                reference + 1,
+                columns.len()
+            ))
+        }
+    };
+
+    column_pairs
+        .into_iter()
+        .map(|(l, r)| {
+            if columns[l].len() != columns[r].len() {
+                return Err(format!(
+                    "columns {} and {} have different lengths",
+                    l + 1,
+                    r + 1
+                ));
+            }
+            let pairs = metric.pairs(&columns[l], &columns[r])?;
+            let score = pairs
+                .iter()
+                .try_fold(0i128, |score, pair| score.checked_add(pair.cost))
+                .ok_or_else(|| {
+                    format!(
+                        "the {metric} score of columns {} and {} is too large",
+                        l + 1,
+                        r + 1
+                    )
+                })?;
+            Ok(ColumnScore {
+                left: l,
+                right: r,
+                score,
+                pairs,
+            })
+        })
+        .collect()
+}
+
+// One line per compared pair of columns (numbered from 1) with its score and worst pairs.
+pub fn report(scores: &[ColumnScore], metric: Metric, worst: usize) -> String {
+    let mut out = String::with_capacity(scores.len() * (64 + worst * 24));
+    for score in scores {
+        let _ = writeln!(
+            out,
+            "columns {} and {}, {metric}: {}",
+            score.left + 1,
+            score.right + 1,
+            score.score
+        );
+        let pairs: Vec<String> = score
+            .worst_pairs(metric, worst)
+            .iter()
+            .map(|pair| format!("{} and {} ({})", pair.left, pair.right, pair.cost))
+            .collect();
+        if !pairs.is_empty() {
+            let _ = writeln!(out, "  worst pairs: {}", pairs.join(", "));
+        }
+    }
+    out
+}
+
+// Any number of columns, but the same number on every line. Blank lines are skipped.
+pub fn parse_columns(input: &str) -> Result<Columns, ParseError> {
+    let mut columns: Vec<Vec<i64>> = Vec::new();
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let fields = fields(line);
+        if columns.is_empty() {
+            columns = vec![Vec::with_capacity(1024); fields.len()];
+        }
+        if fields.len() != columns.len() {
+            return Err(ParseError::new(
+                line,
+                format!("{} values like on the first line", columns.len()),
+            ));
+        }
+        for (column, field) in columns.iter_mut().zip(fields) {
+            column.push(parse_number(field)?);
+        }
+    }
+    Ok(columns
+        .into_iter()
+        .map(|column| column.into_boxed_slice())
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{difference_sum, parse, similarity_score};
+
+    static EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    #[test]
+    fn reconcile_agrees_with_the_puzzle_for_two_columns() {
+        // given
+        let columns = parse_columns(EXAMPLE).expect("expected example to parse");
+        let ids = parse(EXAMPLE).expect("expected example to parse");
+
+        // when
+        let absolute = reconcile(&columns, Pairing::Pairwise, Metric::Absolute);
+        let frequency = reconcile(&columns, Pairing::Pairwise, Metric::FrequencyWeighted);
+
+        // then
+        let score = |scores: Result<Vec<ColumnScore>, String>| scores.map(|s| s[0].score);
+        assert_eq!(score(absolute), Ok(difference_sum(&ids) as i128));
+        assert_eq!(score(frequency), Ok(similarity_score(&ids) as i128));
+    }
+
+    #[test]
+    fn reconcile_compares_columns_with_a_reference() {
+        // given
+        let columns = parse_columns("1,-5\t,9\n2,5,-9\n3,0,0\n").expect("expected CSV to parse");
+
+        // when
+        let scores = reconcile(&columns, Pairing::Reference(0), Metric::Squared)
+            .expect("expected reference column to exist");
+        let ranks = reconcile(&columns, Pairing::Reference(0), Metric::Rank)
+            .expect("expected reference column to exist");
+
+        // then
+        let squared: Vec<(usize, usize, i128)> =
+            scores.iter().map(|s| (s.left, s.right, s.score)).collect();
+        assert_eq!(squared, vec![(1, 0, 36 + 4 + 4), (2, 0, 100 + 4 + 36)]);
+        assert_eq!(ranks[0].score, 1 + 1);
+        assert_eq!(
+            scores[1].worst_pairs(Metric::Squared, 1),
+            vec![Pair {
+                left: -9,
+                right: 1,
+                cost: 100
+            }]
+        );
+        assert!(reconcile(&columns, Pairing::Reference(3), Metric::Rank).is_err());
+    }
+
+    #[test]
+    fn report_lists_the_worst_pairs() {
+        // given
+        let columns = parse_columns(EXAMPLE).expect("expected example to parse");
+        let scores = reconcile(&columns, Pairing::Pairwise, Metric::Absolute)
+            .expect("expected columns to be comparable");
+
+        // when
+        let report = report(&scores, Metric::Absolute, 2);
+
+        // then
+        assert_eq!(
+            report,
+            "columns 1 and 2, absolute: 11\n  worst pairs: 4 and 9 (5), 1 and 3 (2)\n"
+        );
+    }
+
+    #[test]
+    fn parse_columns_requires_the_same_number_of_values() {
+        assert!(parse_columns("1 2 3\n4 5\n").is_err());
+        assert_eq!(
+            parse_columns("1\t2\n\n-3\t4\n  \n"),
+            Ok(vec![
+                vec![1, -3].into_boxed_slice(),
+                vec![2, 4].into_boxed_slice()
+            ]
+            .into())
+        );
+    }
+
+    #[test]
+    fn reconcile_reports_squares_that_do_not_fit() {
+        // given
+        let columns: Columns = vec![
+            vec![i64::MIN].into_boxed_slice(),
+            vec![i64::MAX].into_boxed_slice(),
+        ]
+        .into();
+
+        // when
+        let squared = reconcile(&columns, Pairing::Pairwise, Metric::Squared);
+        let absolute = reconcile(&columns, Pairing::Pairwise, Metric::Absolute);
+
+        // then
+        assert!(squared.is_err());
+        assert_eq!(absolute.map(|scores| scores[0].score), Ok(u64::MAX as i128));
+    }
+}
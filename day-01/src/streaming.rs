@@ -0,0 +1,379 @@
+use crate::parse_line;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::env;
+use std::fs::{remove_file, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Both answers of day 1 for lists that may be larger than the memory. The columns are sorted in
+// chunks that are written to temporary files and merged again while reading them back, so only
+// one chunk has to fit into memory. The sums are `u64`, lists of that size overflow the `u32` of
+// `difference_sum` and `similarity_score`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Totals {
+    pub difference_sum: u64,
+    pub similarity_score: u64,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StreamOptions {
+    // lines per sorted chunk, 8 bytes of memory each
+    pub chunk_lines: usize,
+    // where the sorted chunks go, they are removed when done
+    pub temp_dir: PathBuf,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        StreamOptions {
+            chunk_lines: 1 << 24,
+            temp_dir: env::temp_dir(),
+        }
+    }
+}
+
+pub fn reconcile(input: impl BufRead, options: &StreamOptions) -> Result<Totals, String> {
+    let (left, right) = sorted_columns(input, options)?;
+    let io_error = |e: io::Error| format!("unable to read a sorted chunk: {e}");
+
+    let mut difference_sum: u64 = 0;
+    for (l, r) in left
+        .values()
+        .map_err(io_error)?
+        .zip(right.values().map_err(io_error)?)
+    {
+        difference_sum += l.map_err(io_error)?.abs_diff(r.map_err(io_error)?) as u64;
+    }
+    let similarity_score = similarity(
+        left.values().map_err(io_error)?,
+        right.values().map_err(io_error)?,
+    )
+    .map_err(io_error)?;
+
+    Ok(Totals {
+        difference_sum,
+        similarity_score,
+    })
+}
+
+// The right list is sorted as well, so the right ids equal to a left id directly follow the
+// ones equal to the previous (smaller or equal) left id.
+fn similarity(
+    left: impl Iterator<Item = io::Result<u32>>,
+    mut right: impl Iterator<Item = io::Result<u32>>,
+) -> io::Result<u64> {
+    let mut next_right = right.next().transpose()?;
+    let mut counted: Option<(u32, u64)> = None;
+    let mut score: u64 = 0;
+    for l in left {
+        let l = l?;
+        let count = match counted {
+            Some((id, count)) if id == l => count,
+            _ => {
+                while next_right.is_some_and(|r| r < l) {
+                    next_right = right.next().transpose()?;
+                }
+                let mut count = 0;
+                while next_right == Some(l) {
+                    count += 1;
+                    next_right = right.next().transpose()?;
+                }
+                counted = Some((l, count));
+                count
+            }
+        };
+        score += l as u64 * count;
+    }
+    Ok(score)
+}
+
+fn sorted_columns(
+    mut input: impl BufRead,
+    options: &StreamOptions,
+) -> Result<(Column, Column), String> {
+    let chunk_lines = options.chunk_lines.max(1);
+    let mut left: Vec<u32> = Vec::with_capacity(chunk_lines.min(1 << 16));
+    let mut right: Vec<u32> = Vec::with_capacity(chunk_lines.min(1 << 16));
+    let (mut left_runs, mut right_runs): (Vec<Run>, Vec<Run>) = (Vec::new(), Vec::new());
+    let spill = |left: &mut Vec<u32>, right: &mut Vec<u32>| -> Result<(Run, Run), String> {
+        let runs = (
+            Run::write(&options.temp_dir, left)?,
+            Run::write(&options.temp_dir, right)?,
+        );
+        left.clear();
+        right.clear();
+        Ok(runs)
+    };
+
+    let mut line = String::with_capacity(32);
+    let mut line_number = 0;
+    loop {
+        line.clear();
+        let read = input
+            .read_line(&mut line)
+            .map_err(|e| format!("unable to read the input: {e}"))?;
+        if read == 0 {
+            break;
+        }
+        line_number += 1;
+        let content = line.strip_suffix('\n').unwrap_or(&line);
+        let content = content.strip_suffix('\r').unwrap_or(content);
+        let (l, r) = parse_line(content).map_err(|e| {
+            // every line is parsed on its own, so the line number has to be fixed up
+            let mut e = e.locate(content).with_day(1);
+            if let Some(location) = &mut e.location {
+                location.line = line_number;
+            }
+            e.to_string()
+        })?;
+        left.push(l);
+        right.push(r);
+        if left.len() == chunk_lines {
+            let (left_run, right_run) = spill(&mut left, &mut right)?;
+            left_runs.push(left_run);
+            right_runs.push(right_run);
+        }
+    }
+
+    // without any full chunk there is no need for files at all
+    if left_runs.is_empty() {
+        left.sort_unstable();
+        right.sort_unstable();
+        return Ok((Column::Memory(left), Column::Memory(right)));
+    }
+    if !left.is_empty() {
+        let (left_run, right_run) = spill(&mut left, &mut right)?;
+        left_runs.push(left_run);
+        right_runs.push(right_run);
+    }
+    Ok((
+        Column::Runs(merge_passes(left_runs, &options.temp_dir, MAX_FAN_IN)?),
+        Column::Runs(merge_passes(right_runs, &options.temp_dir, MAX_FAN_IN)?),
+    ))
+}
+
+// the most runs that are open at the same time, to stay well below the limit of open files
+const MAX_FAN_IN: usize = 64;
+
+// Merges groups of `fan_in` runs into one until there are at most `fan_in` runs left.
+fn merge_passes(mut runs: Vec<Run>, dir: &Path, fan_in: usize) -> Result<Vec<Run>, String> {
+    let fan_in = fan_in.max(2);
+    while runs.len() > fan_in {
+        let mut merged: Vec<Run> = Vec::with_capacity(runs.len().div_ceil(fan_in));
+        for group in runs.chunks(fan_in) {
+            let merge =
+                Merge::new(group).map_err(|e| format!("unable to read a sorted chunk: {e}"))?;
+            merged.push(Run::write_sorted(dir, merge)?);
+        }
+        // the merged runs are removed here
+        runs = merged;
+    }
+    Ok(runs)
+}
+
+enum Column {
+    Memory(Vec<u32>),
+    Runs(Vec<Run>),
+}
+
+impl Column {
+    // in ascending order, can be called several times
+    fn values(&self) -> io::Result<Box<dyn Iterator<Item = io::Result<u32>> + '_>> {
+        match self {
+            Column::Memory(values) => Ok(Box::new(values.iter().map(|v| Ok(*v)))),
+            Column::Runs(runs) => Ok(Box::new(Merge::new(runs)?)),
+        }
+    }
+}
+
+static RUN_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+// A sorted chunk of a column in a temporary file, as little endian `u32`s. The file is removed
+// when the run is dropped.
+struct Run {
+    path: PathBuf,
+    len: usize,
+}
+
+impl Run {
+    fn write(dir: &Path, values: &mut [u32]) -> Result<Run, String> {
+        values.sort_unstable();
+        Run::write_sorted(dir, values.iter().map(|value| Ok(*value)))
+    }
+
+    fn write_sorted(
+        dir: &Path,
+        values: impl Iterator<Item = io::Result<u32>>,
+    ) -> Result<Run, String> {
+        let name = format!(
+            "day-01-{}-{}.run",
+            process::id(),
+            RUN_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let mut run = Run {
+            path: dir.join(name),
+            len: 0,
+        };
+        let write_error = |e: io::Error| format!("unable to write '{}': {e}", run.path.display());
+        let mut out = BufWriter::new(File::create(&run.path).map_err(write_error)?);
+        let mut len = 0;
+        for value in values {
+            let value = value.map_err(|e| format!("unable to read a sorted chunk: {e}"))?;
+            out.write_all(&value.to_le_bytes()).map_err(write_error)?;
+            len += 1;
+        }
+        out.flush().map_err(write_error)?;
+        run.len = len;
+        Ok(run)
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = remove_file(&self.path);
+    }
+}
+
+struct RunReader {
+    reader: BufReader<File>,
+    remaining: usize,
+}
+
+impl RunReader {
+    fn next(&mut self) -> io::Result<Option<u32>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        let mut bytes = [0; 4];
+        self.reader.read_exact(&mut bytes)?;
+        self.remaining -= 1;
+        Ok(Some(u32::from_le_bytes(bytes)))
+    }
+}
+
+// k-way merge of sorted runs, the heap holds the smallest unread value of every run
+struct Merge {
+    readers: Vec<RunReader>,
+    heap: BinaryHeap<Reverse<(u32, usize)>>,
+}
+
+impl Merge {
+    fn new(runs: &[Run]) -> io::Result<Merge> {
+        let mut readers: Vec<RunReader> = Vec::with_capacity(runs.len());
+        let mut heap = BinaryHeap::with_capacity(runs.len());
+        for (i, run) in runs.iter().enumerate() {
+            let mut reader = RunReader {
+                reader: BufReader::new(File::open(&run.path)?),
+                remaining: run.len,
+            };
+            if let Some(value) = reader.next()? {
+                heap.push(Reverse((value, i)));
+            }
+            readers.push(reader);
+        }
+        Ok(Merge { readers, heap })
+    }
+}
+
+impl Iterator for Merge {
+    type Item = io::Result<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((value, i)) = self.heap.pop()?;
+        match self.readers[i].next() {
+            Ok(Some(next)) => self.heap.push(Reverse((next, i))),
+            Ok(None) => (),
+            Err(e) => return Some(Err(e)),
+        }
+        Some(Ok(value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{difference_sum, similarity_score};
+    use common::random::check_property;
+    use std::fs::read_dir;
+
+    #[test]
+    fn reconcile_matches_the_in_memory_solution() {
+        let temp_dir = env::temp_dir().join(format!("day-01-test-{}", process::id()));
+        std::fs::create_dir_all(&temp_dir).expect("expected temp dir to be created");
+
+        check_property(100, |rng| {
+            // given
+            let ids: Box<[(u32, u32)]> = (0..rng.range_usize(0..200))
+                .map(|_| (rng.range(0..50) as u32, rng.range(0..50) as u32))
+                .collect();
+            let input: String = ids.iter().map(|(l, r)| format!("{l}   {r}\n")).collect();
+            let options = StreamOptions {
+                chunk_lines: rng.range_usize(1..40),
+                temp_dir: temp_dir.clone(),
+            };
+
+            // when
+            let totals = reconcile(input.as_bytes(), &options)?;
+
+            // then
+            let expected = Totals {
+                difference_sum: difference_sum(&ids) as u64,
+                similarity_score: similarity_score(&ids) as u64,
+            };
+            if totals != expected {
+                return Err(format!("expected {expected:?}, got {totals:?} for {ids:?}"));
+            }
+            Ok(())
+        });
+
+        let leftovers = read_dir(&temp_dir)
+            .expect("expected temp dir to exist")
+            .count();
+        let _ = std::fs::remove_dir(&temp_dir);
+        assert_eq!(leftovers, 0, "sorted chunks were not removed");
+    }
+
+    #[test]
+    fn merge_passes_limit_the_number_of_runs() {
+        // given
+        let temp_dir = env::temp_dir().join(format!("day-01-merge-test-{}", process::id()));
+        std::fs::create_dir_all(&temp_dir).expect("expected temp dir to be created");
+        let mut values: Vec<u32> = (0..100).map(|v| (v * 37) % 101).collect();
+        let runs: Vec<Run> = values
+            .chunks_mut(7)
+            .map(|chunk| Run::write(&temp_dir, chunk))
+            .collect::<Result<_, String>>()
+            .expect("expected runs to be written");
+
+        // when
+        let runs = merge_passes(runs, &temp_dir, 3).expect("expected runs to be merged");
+
+        // then
+        assert!(runs.len() <= 3, "{} runs left", runs.len());
+        let merged: Vec<u32> = Merge::new(&runs)
+            .expect("expected runs to be readable")
+            .collect::<io::Result<_>>()
+            .expect("expected runs to be readable");
+        values.sort_unstable();
+        assert_eq!(merged, values);
+        drop(runs);
+        let leftovers = read_dir(&temp_dir)
+            .expect("expected temp dir to exist")
+            .count();
+        let _ = std::fs::remove_dir(&temp_dir);
+        assert_eq!(leftovers, 0, "merged runs were not removed");
+    }
+
+    #[test]
+    fn reconcile_reports_the_line_of_an_error() {
+        // when
+        let result = reconcile("3   4\r\n4   x\n".as_bytes(), &StreamOptions::default());
+
+        // then
+        let e = result.expect_err("expected an error for 'x'");
+        assert!(e.starts_with("day 1, line 2, column 5:"), "{e}");
+    }
+}
@@ -0,0 +1,375 @@
+use common::random::{GenOptions, Rng};
+use common::{parse_number, Answer, ParseError, Solution};
+use policy::{Direction, SafetyPolicy};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::iter::successors;
+
+pub mod policy;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input<'a> = Box<[Box<[u32]>]>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(count_safe(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(count_dampened_safe(input).into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(1000)))
+    }
+}
+
+pub fn count_safe(reports: &[Box<[u32]>]) -> usize {
+    reports.iter().filter(|report| is_safe(report)).count()
+}
+
+fn is_safe(report: &[u32]) -> bool {
+    let (c1, c2) = dampener_candidates(report);
+    c1.is_none() || c2.is_none()
+}
+
+fn dampener_candidates(report: &[u32]) -> (Option<usize>, Option<usize>) {
+    let candidate_1 = report
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0] >= pair[1] || pair[1] - pair[0] > 3)
+        .map(|(i, _)| i)
+        .next();
+    let candidate_2 = report
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[1] >= pair[0] || pair[0] - pair[1] > 3)
+        .map(|(i, _)| i)
+        .next();
+    (candidate_1, candidate_2)
+}
+
+pub fn count_dampened_safe(reports: &[Box<[u32]>]) -> usize {
+    reports
+        .iter()
+        .filter(|report| is_dampened_safe(report))
+        .count()
+}
+
+fn is_dampened_safe(report: &[u32]) -> bool {
+    let (c1, c2) = dampener_candidates(report);
+    if c1.is_none() || c2.is_none() {
+        return true;
+    }
+    c1.iter()
+        .flat_map(|i| [*i, i + 1])
+        .chain(c2.iter().flat_map(|i| [*i, i + 1]))
+        .any(|unsafe_i| {
+            let dampened_report: Box<[u32]> = report
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != unsafe_i)
+                .map(|(_, level)| *level)
+                .collect();
+            is_safe(&dampened_report)
+        })
+}
+
+pub fn count_safe_with_budget(reports: &[Box<[u32]>], k: usize) -> usize {
+    reports
+        .iter()
+        .filter(|report| is_k_dampened_safe(report, k))
+        .count()
+}
+
+pub fn is_k_dampened_safe(report: &[u32], k: usize) -> bool {
+    min_removals(report) <= k
+}
+
+pub fn min_removals(report: &[u32]) -> usize {
+    removals(report, &SafetyPolicy::default()).len()
+}
+
+// The fewest levels (by index) to remove for the rest of the report to follow the policy. The
+// levels that remain form the longest safe subsequence, in any of the allowed directions.
+pub fn removals(report: &[u32], policy: &SafetyPolicy) -> Vec<usize> {
+    let increasing = match policy.direction {
+        Direction::Decreasing => Vec::new(),
+        _ => longest_increasing_safe(report, 0..report.len(), policy),
+    };
+    let decreasing = match policy.direction {
+        Direction::Increasing => Vec::new(),
+        _ => longest_increasing_safe(report, (0..report.len()).rev(), policy),
+    };
+    let mut kept = vec![false; report.len()];
+    for i in if decreasing.len() > increasing.len() {
+        decreasing
+    } else {
+        increasing
+    } {
+        kept[i] = true;
+    }
+    (0..report.len()).filter(|i| !kept[*i]).collect()
+}
+
+// A level can follow any earlier level a step of the policy below it, so it is enough to
+// remember the longest subsequence ending in every value seen so far instead of comparing with
+// all earlier levels. Of equally long subsequences the one ending at the larger index is taken, so
+// the result does not depend on the order of the hash map. Returns the indices of the levels.
+fn longest_increasing_safe(
+    report: &[u32],
+    order: impl Iterator<Item = usize>,
+    policy: &SafetyPolicy,
+) -> Vec<usize> {
+    let steps = policy.min_step.max(1)..=policy.max_step;
+    let step_count = (*steps.end() as usize + 1).saturating_sub(*steps.start() as usize);
+    // value -> (length, index) of the longest subsequence ending in it
+    let mut longest_ending_in: HashMap<u32, (usize, usize)> = HashMap::new();
+    let mut previous: Vec<Option<usize>> = vec![None; report.len()];
+    let mut longest: Option<(usize, usize)> = None;
+    for i in order {
+        let level = report[i];
+        let plateau = policy
+            .allow_plateaus
+            .then(|| longest_ending_in.get(&level).copied())
+            .flatten();
+        // with large steps there are fewer values seen so far than steps to try
+        let before = if step_count <= longest_ending_in.len() {
+            steps
+                .clone()
+                .filter_map(|step| level.checked_sub(step))
+                .filter_map(|value| longest_ending_in.get(&value).copied())
+                .max()
+        } else {
+            longest_ending_in
+                .iter()
+                .filter(|(value, _)| {
+                    level
+                        .checked_sub(**value)
+                        .is_some_and(|s| steps.contains(&s))
+                })
+                .map(|(_, ending)| *ending)
+                .max()
+        };
+        let before = plateau.max(before);
+        previous[i] = before.map(|(_, index)| index);
+        let ending = (before.map_or(0, |(length, _)| length) + 1, i);
+        let entry = longest_ending_in.entry(level).or_insert(ending);
+        *entry = (*entry).max(ending);
+        longest = longest.max(Some(ending));
+    }
+
+    let mut indices: Vec<usize> =
+        successors(longest.map(|(_, index)| index), |i| previous[*i]).collect();
+    indices.sort_unstable();
+    indices
+}
+
+pub fn parse(input: &str) -> Result<Box<[Box<[u32]>]>, ParseError> {
+    input.lines().map(parse_report).collect()
+}
+
+fn parse_report(line: &str) -> Result<Box<[u32]>, ParseError> {
+    line.split_whitespace().map(parse_number).collect()
+}
+
+// Reports of 5 to 8 levels, mostly increasing or decreasing by 1 to 3. Some levels break the rule
+// (a jump, no change or a change of direction), so all kinds of reports appear.
+pub fn generate(rng: &mut Rng, lines: usize) -> String {
+    let mut input = String::with_capacity(lines * 20);
+    for _ in 0..lines {
+        let len = rng.range_usize(5..9);
+        let direction = if rng.chance(1, 2) { 1 } else { -1 };
+        let mut level = rng.range(30..70);
+        for i in 0..len {
+            if i > 0 {
+                input.push(' ');
+                let step = match rng.below(12) {
+                    0 => 0,
+                    1 => rng.range(4..8),
+                    2 => -rng.range(1..4),
+                    _ => rng.range(1..4),
+                };
+                level += direction * step;
+            }
+            let _ = write!(input, "{level}");
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::random::check_property;
+
+    static INPUT: &str = r#"7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+"#;
+
+    #[test]
+    fn count_safe_counts_correctly() {
+        // given
+        let reports = parse(INPUT).expect("expected successful parsing");
+
+        // when
+        let count = count_safe(&reports);
+
+        // then
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn count_dampened_safe_counts_correctly() {
+        // given
+        let reports = parse(INPUT).expect("expected successful parsing");
+
+        // when
+        let count = count_dampened_safe(&reports);
+
+        // then
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn is_dampened_safe_works_if_first_index_needs_to_be_removed() {
+        // given
+        let report = &[1, 2, 4, 3, 4];
+
+        // when
+        let safe = is_dampened_safe(report);
+
+        // then
+        assert!(safe);
+    }
+
+    #[test]
+    fn is_dampened_safe_works_if_first_report_is_in_wrong_order() {
+        // given
+        let report = &[2, 1, 2, 3, 4];
+
+        // when
+        let safe = is_dampened_safe(report);
+
+        // then
+        assert!(safe);
+    }
+
+    #[test]
+    fn is_dampened_safe_works_if_first_report_is_too_far_away() {
+        // given
+        let report = &[1, 8, 9, 10];
+
+        // when
+        let safe = is_dampened_safe(report);
+
+        // then
+        assert!(safe);
+    }
+
+    #[test]
+    fn is_dampened_safe_works_if_last_report_is_in_wrong_order() {
+        // given
+        let report = &[1, 2, 3, 4, 3];
+
+        // when
+        let safe = is_dampened_safe(report);
+
+        // then
+        assert!(safe);
+    }
+
+    #[test]
+    fn is_dampened_safe_works_if_last_report_is_too_far_away() {
+        // given
+        let report = &[1, 2, 3, 4, 8];
+
+        // when
+        let safe = is_dampened_safe(report);
+
+        // then
+        assert!(safe);
+    }
+
+    #[test]
+    fn is_k_dampened_safe_agrees_with_the_dampener() {
+        check_property(500, |rng| {
+            // given
+            let report: Box<[u32]> = (0..rng.range_usize(1..9))
+                .map(|_| rng.range(1..12) as u32)
+                .collect();
+
+            // when
+            let safe = [
+                is_k_dampened_safe(&report, 0),
+                is_k_dampened_safe(&report, 1),
+            ];
+
+            // then
+            let expected = [is_safe(&report), is_dampened_safe(&report)];
+            if safe != expected {
+                return Err(format!(
+                    "expected {expected:?}, got {safe:?} for {report:?}"
+                ));
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn min_removals_finds_the_fewest_removals() {
+        check_property(300, |rng| {
+            // given
+            let report: Box<[u32]> = (0..rng.range_usize(1..11))
+                .map(|_| rng.range(1..15) as u32)
+                .collect();
+
+            // when
+            let removals = min_removals(&report);
+
+            // then
+            let expected = (0..1u32 << report.len())
+                .filter(|kept| {
+                    let kept: Box<[u32]> = (0..report.len())
+                        .filter(|i| kept & (1 << i) != 0)
+                        .map(|i| report[i])
+                        .collect();
+                    !kept.is_empty() && is_safe(&kept)
+                })
+                .map(|kept| report.len() - kept.count_ones() as usize)
+                .min();
+            if Some(removals) != expected {
+                return Err(format!(
+                    "expected {expected:?}, got {removals} for {report:?}"
+                ));
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn count_safe_with_budget_counts_correctly() {
+        // given
+        let reports = parse(INPUT).expect("expected successful parsing");
+
+        // when
+        let counts: Vec<usize> = (0..3)
+            .map(|k| count_safe_with_budget(&reports, k))
+            .collect();
+
+        // then
+        assert_eq!(counts, vec![2, 4, 6]);
+    }
+}
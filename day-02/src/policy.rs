@@ -0,0 +1,370 @@
+use crate::removals;
+use std::fmt::{self, Write};
+
+// The rules a report has to follow to be safe. The default is the one of the puzzle: strictly
+// increasing or decreasing by 1 to 3, with one level that the dampener may remove.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct SafetyPolicy {
+    pub min_step: u32,
+    pub max_step: u32,
+    // equal neighbouring levels, they neither count as a step nor decide the direction
+    pub allow_plateaus: bool,
+    pub direction: Direction,
+    // how many levels the dampener may remove
+    pub max_removals: usize,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    // whichever the first step goes in, but the same for all steps
+    Either,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        SafetyPolicy {
+            min_step: 1,
+            max_step: 3,
+            allow_plateaus: false,
+            direction: Direction::Either,
+            max_removals: 1,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Reason {
+    Plateau,
+    WrongDirection,
+    StepTooSmall(u32),
+    StepTooLarge(u32),
+}
+
+// The first level that breaks the policy, compared with the level before it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Violation {
+    pub index: usize,
+    pub reason: Reason,
+}
+
+impl SafetyPolicy {
+    // Reads `--min-step <n>`, `--max-step <n>`, `--allow-plateaus`, `--direction <direction>` and
+    // `--max-removals <n>` and returns the other arguments.
+    pub fn from_args(args: &[String]) -> Result<(SafetyPolicy, Vec<String>), String> {
+        let mut policy = SafetyPolicy::default();
+        let mut rest = Vec::with_capacity(args.len());
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("expected a value after '{arg}'"))
+            };
+            match arg.as_str() {
+                "--min-step" | "--max-step" => {
+                    let n = value()?;
+                    let step = n
+                        .parse()
+                        .map_err(|_| format!("expected a step size, found '{n}'"))?;
+                    if arg == "--min-step" {
+                        policy.min_step = step;
+                    } else {
+                        policy.max_step = step;
+                    }
+                }
+                "--max-removals" => {
+                    let n = value()?;
+                    policy.max_removals = n
+                        .parse()
+                        .map_err(|_| format!("expected a number of levels, found '{n}'"))?;
+                }
+                "--allow-plateaus" => policy.allow_plateaus = true,
+                "--direction" => {
+                    policy.direction = match value()?.as_str() {
+                        "increasing" => Direction::Increasing,
+                        "decreasing" => Direction::Decreasing,
+                        "either" => Direction::Either,
+                        other => {
+                            return Err(format!(
+                        "unknown direction '{other}', expected increasing, decreasing or either"
+                    ))
+                        }
+                    }
+                }
+                _ => rest.push(arg.clone()),
+            }
+        }
+        if policy.min_step > policy.max_step {
+            return Err(format!(
+                "the minimum step {} is larger than the maximum step {}",
+                policy.min_step, policy.max_step
+            ));
+        }
+        Ok((policy, rest))
+    }
+
+    pub fn check(&self, report: &[u32]) -> Result<(), Violation> {
+        let mut direction = self.direction;
+        for (i, pair) in report.windows(2).enumerate() {
+            let violation = |reason| {
+                Err(Violation {
+                    index: i + 1,
+                    reason,
+                })
+            };
+            if pair[0] == pair[1] {
+                if !self.allow_plateaus {
+                    return violation(Reason::Plateau);
+                }
+                continue;
+            }
+            let step_direction = if pair[1] > pair[0] {
+                Direction::Increasing
+            } else {
+                Direction::Decreasing
+            };
+            if direction == Direction::Either {
+                direction = step_direction;
+            } else if direction != step_direction {
+                return violation(Reason::WrongDirection);
+            }
+            let step = pair[0].abs_diff(pair[1]);
+            if step < self.min_step {
+                return violation(Reason::StepTooSmall(step));
+            }
+            if step > self.max_step {
+                return violation(Reason::StepTooLarge(step));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_safe(&self, report: &[u32]) -> bool {
+        removals(report, self).is_empty()
+    }
+
+    // The fewest levels to remove for the report to be safe, if the dampener may remove that many.
+    pub fn dampen(&self, report: &[u32]) -> Option<Vec<usize>> {
+        Some(removals(report, self)).filter(|removed| removed.len() <= self.max_removals)
+    }
+
+    pub fn is_dampened_safe(&self, report: &[u32]) -> bool {
+        self.dampen(report).is_some()
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::Plateau => write!(f, "it equals the previous level"),
+            Reason::WrongDirection => write!(f, "it changes the direction"),
+            Reason::StepTooSmall(step) => write!(f, "the step of {step} is too small"),
+            Reason::StepTooLarge(step) => write!(f, "the step of {step} is too large"),
+        }
+    }
+}
+
+// One line for every report that is not safe as it is, with levels and reports counted from 1.
+pub fn explain(reports: &[Box<[u32]>], policy: &SafetyPolicy) -> String {
+    let mut out = String::new();
+    for (i, report) in reports.iter().enumerate() {
+        let Err(violation) = policy.check(report) else {
+            continue;
+        };
+        let _ = write!(
+            out,
+            "report {}: level {} ({}) is unsafe, {}",
+            i + 1,
+            violation.index + 1,
+            report[violation.index],
+            violation.reason
+        );
+        let _ = match policy.dampen(report) {
+            Some(removed) => {
+                let levels: Vec<String> = removed
+                    .iter()
+                    .map(|i| format!("{} ({})", i + 1, report[*i]))
+                    .collect();
+                let plural = if removed.len() == 1 { "" } else { "s" };
+                writeln!(
+                    out,
+                    "; safe after removing level{plural} {}",
+                    levels.join(", ")
+                )
+            }
+            None if policy.max_removals == 0 => writeln!(out),
+            None if policy.max_removals == 1 => {
+                writeln!(out, "; removing one level does not help")
+            }
+            None => writeln!(
+                out,
+                "; removing up to {} levels does not help",
+                policy.max_removals
+            ),
+        };
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{count_dampened_safe, count_safe, is_dampened_safe, is_safe, parse};
+    use common::random::check_property;
+
+    static INPUT: &str = r#"7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+"#;
+
+    #[test]
+    fn default_policy_is_the_one_of_the_puzzle() {
+        check_property(500, |rng| {
+            // given
+            let report: Box<[u32]> = (0..rng.range_usize(1..8))
+                .map(|_| rng.range(1..10) as u32)
+                .collect();
+            let policy = SafetyPolicy::default();
+
+            // when
+            let safe = [policy.is_safe(&report), policy.is_dampened_safe(&report)];
+
+            // then
+            let expected = [is_safe(&report), is_dampened_safe(&report)];
+            if safe != expected {
+                return Err(format!(
+                    "expected {expected:?}, got {safe:?} for {report:?}"
+                ));
+            }
+            Ok(())
+        });
+        let reports = parse(INPUT).expect("expected successful parsing");
+        assert_eq!(count_safe(&reports), 2);
+        assert_eq!(count_dampened_safe(&reports), 4);
+    }
+
+    #[test]
+    fn removals_are_the_fewest_that_make_a_report_safe() {
+        check_property(500, |rng| {
+            // given
+            let report: Box<[u32]> = (0..rng.range_usize(0..8))
+                .map(|_| rng.range(1..12) as u32)
+                .collect();
+            let min_step = rng.range(0..4) as u32;
+            let policy = SafetyPolicy {
+                min_step,
+                max_step: min_step + rng.range(0..4) as u32,
+                allow_plateaus: rng.chance(1, 2),
+                direction: *rng.choose(&[
+                    Direction::Increasing,
+                    Direction::Decreasing,
+                    Direction::Either,
+                ]),
+                max_removals: rng.range_usize(0..3),
+            };
+
+            // when
+            let removed = removals(&report, &policy);
+
+            // then
+            let fewest = (0..1usize << report.len())
+                .filter(|mask| {
+                    let kept: Vec<u32> = (0..report.len())
+                        .filter(|i| mask & (1 << i) == 0)
+                        .map(|i| report[i])
+                        .collect();
+                    policy.check(&kept).is_ok()
+                })
+                .map(|mask| mask.count_ones() as usize)
+                .min();
+            let kept: Vec<u32> = (0..report.len())
+                .filter(|i| !removed.contains(i))
+                .map(|i| report[i])
+                .collect();
+            if Some(removed.len()) != fewest || policy.check(&kept).is_err() {
+                return Err(format!(
+                    "expected {fewest:?} removals, got {removed:?} for {report:?} and {policy:?}"
+                ));
+            }
+            if policy.is_dampened_safe(&report) != (removed.len() <= policy.max_removals) {
+                return Err(format!("wrong budget for {report:?} and {policy:?}"));
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn from_args_reads_the_policy() {
+        // given
+        let args: Vec<String> = [
+            "--max-step",
+            "5",
+            "--allow-plateaus",
+            "input.txt",
+            "--direction",
+            "increasing",
+            "--max-removals",
+            "2",
+        ]
+        .map(String::from)
+        .into();
+
+        // when
+        let (policy, rest) = SafetyPolicy::from_args(&args).expect("expected valid flags");
+
+        // then
+        assert_eq!(
+            policy,
+            SafetyPolicy {
+                min_step: 1,
+                max_step: 5,
+                allow_plateaus: true,
+                direction: Direction::Increasing,
+                max_removals: 2,
+            }
+        );
+        assert_eq!(rest, vec!["input.txt".to_owned()]);
+        assert!(policy.is_safe(&[1, 1, 6, 7]));
+        assert!(!policy.is_safe(&[7, 6]));
+        assert!(SafetyPolicy::from_args(&["--direction".to_owned(), "up".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn explain_names_the_first_unsafe_level_and_the_removed_one() {
+        // given
+        let reports = parse(INPUT).expect("expected successful parsing");
+
+        // when
+        let explanation = explain(&reports, &SafetyPolicy::default());
+
+        // then
+        assert_eq!(
+            explanation,
+            "report 2: level 3 (7) is unsafe, the step of 5 is too large; removing one level does not help
+report 3: level 4 (2) is unsafe, the step of 4 is too large; removing one level does not help
+report 4: level 3 (2) is unsafe, it changes the direction; safe after removing level 2 (3)
+report 5: level 4 (4) is unsafe, it equals the previous level; safe after removing level 3 (4)
+"
+        );
+        let budget = SafetyPolicy {
+            max_removals: 2,
+            ..SafetyPolicy::default()
+        };
+        assert!(explain(&reports[1..2], &budget)
+            .ends_with("; safe after removing levels 1 (1), 2 (2)\n"));
+        assert!(explain(&reports[2..3], &budget)
+            .ends_with("; safe after removing levels 4 (2), 5 (1)\n"));
+        assert!(explain(
+            &reports[2..3],
+            &SafetyPolicy {
+                max_step: 1,
+                ..budget
+            }
+        )
+        .ends_with("; removing up to 2 levels does not help\n"));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::registry::{InstructionSet, Reduction};
+use crate::scanner::{scan_with, Token, TokenKind};
+use std::fmt::Write;
+
+// A recognised instruction, its value (of value instructions) and whether value instructions
+// were enabled after it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Step {
+    pub token: Token,
+    pub value: Option<i64>,
+    pub enabled: bool,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Execution {
+    // of all value instructions (part 1 with the puzzle's instructions)
+    pub all: i64,
+    // of the value instructions that were enabled (part 2)
+    pub enabled: i64,
+    pub steps: Vec<Step>,
+}
+
+// Runs the instructions of the token stream and reduces the values of the value instructions.
+// `Call` tokens are run with the semantics of `instructions`, the ones they were scanned with.
+pub fn execute(tokens: &[Token], instructions: &InstructionSet, reduction: Reduction) -> Execution {
+    let mut execution = Execution {
+        all: reduction.initial,
+        enabled: reduction.initial,
+        steps: Vec::with_capacity(tokens.len()),
+    };
+    let mut enabled = true;
+    for token in tokens {
+        let value = match &token.kind {
+            TokenKind::Mul(l, r) => Some(l.wrapping_mul(*r)),
+            TokenKind::Do => {
+                enabled = true;
+                None
+            }
+            TokenKind::Dont => {
+                enabled = false;
+                None
+            }
+            TokenKind::Call(call) => {
+                if let Some(on) = instructions.toggle(call) {
+                    enabled = on;
+                }
+                instructions.value(call)
+            }
+            TokenKind::Corrupted => continue,
+        };
+        if let Some(value) = value {
+            execution.all = (reduction.combine)(execution.all, value);
+            if enabled {
+                execution.enabled = (reduction.combine)(execution.enabled, value);
+            }
+        }
+        execution.steps.push(Step {
+            token: token.clone(),
+            value,
+            enabled,
+        });
+    }
+    execution
+}
+
+// scans the memory and runs it
+pub fn evaluate(memory: &str, instructions: &InstructionSet, reduction: Reduction) -> Execution {
+    execute(&scan_with(memory, instructions), instructions, reduction)
+}
+
+// One line per instruction with its byte offset in the memory, the instruction as written and
+// whether value instructions were enabled after it.
+pub fn trace(memory: &str, execution: &Execution) -> String {
+    // nested instructions can be longer than the usual column
+    let width = execution
+        .steps
+        .iter()
+        .map(|step| step.token.span.len() + 1)
+        .max()
+        .unwrap_or(0)
+        .max(14);
+    let mut out = String::with_capacity(execution.steps.len() * 32);
+    for step in &execution.steps {
+        let state = if step.enabled { "enabled" } else { "disabled" };
+        let _ = writeln!(
+            out,
+            "{:>8}  {:<width$}{state}",
+            step.token.span.start,
+            &memory[step.token.span.clone()]
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::registry::Semantics;
+
+    static EXAMPLE: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn trace_lists_every_instruction() {
+        // when
+        let execution = evaluate(EXAMPLE, &InstructionSet::puzzle(), Reduction::SUM);
+
+        // then
+        assert_eq!(execution.all, 161);
+        assert_eq!(execution.enabled, 48);
+        assert_eq!(
+            trace(EXAMPLE, &execution),
+            "       1  mul(2,4)      enabled
+      20  don't()       disabled
+      28  mul(5,5)      disabled
+      48  mul(11,8)     disabled
+      59  do()          enabled
+      64  mul(8,5)      enabled
+"
+        );
+    }
+
+    #[test]
+    fn execute_supports_new_and_nested_instructions() {
+        // given
+        let instructions = InstructionSet::puzzle()
+            .define(
+                "add",
+                2,
+                Semantics::Value(|args| args[0].wrapping_add(args[1])),
+            )
+            .define("neg", 1, Semantics::Value(|args| args[0].wrapping_neg()))
+            .define("if", 1, Semantics::ToggleIf(|args| args[0] % 2 == 0))
+            .nested(true);
+
+        // when
+        let execution = evaluate(
+            "mul(mul(1,2),3)..add(neg(4),1)if(3)mul(9,9)if(add(1,1))neg(7)",
+            &instructions,
+            Reduction::SUM,
+        );
+
+        // then
+        let values: Vec<Option<i64>> = execution.steps.iter().map(|s| s.value).collect();
+        assert_eq!(values, [Some(6), Some(-3), None, Some(81), None, Some(-7)]);
+        assert_eq!(execution.all, 6 - 3 + 81 - 7);
+        assert_eq!(execution.enabled, 6 - 3 - 7);
+        assert_eq!(execution.steps[1].token.span, 17..30);
+    }
+
+    #[test]
+    fn execute_uses_replaced_puzzle_instructions() {
+        // given
+        let instructions = InstructionSet::puzzle().define(
+            "mul",
+            2,
+            Semantics::Value(|args| args[0].wrapping_add(args[1])),
+        );
+
+        // when
+        let execution = evaluate("mul(2,3)don't()mul(4,5)", &instructions, Reduction::SUM);
+
+        // then
+        assert_eq!((execution.all, execution.enabled), (14, 5));
+    }
+
+    #[test]
+    fn execute_only_finds_inner_instructions_without_nesting() {
+        // when
+        let execution = evaluate(
+            "mul(mul(1,2),3)mul(4,5)",
+            &InstructionSet::puzzle(),
+            Reduction::MAX,
+        );
+
+        // then
+        let values: Vec<Option<i64>> = execution.steps.iter().map(|s| s.value).collect();
+        assert_eq!(values, [Some(2), Some(20)]);
+        assert_eq!(execution.all, 20);
+    }
+}
@@ -0,0 +1,136 @@
+use common::random::{GenOptions, Rng};
+use common::{Answer, ParseError, Solution};
+use interpreter::execute;
+use registry::{InstructionSet, Reduction};
+use scanner::{scan, TokenKind};
+use std::fmt::Write;
+
+pub mod interpreter;
+pub mod registry;
+pub mod scanner;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(
+            execute(&scan(input), &InstructionSet::puzzle(), Reduction::SUM)
+                .all
+                .into(),
+        )
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(
+            execute(&scan(input), &InstructionSet::puzzle(), Reduction::SUM)
+                .enabled
+                .into(),
+        )
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(18000)))
+    }
+}
+
+pub fn parse_ignore_corrupted(memory: &str) -> Box<[(i64, i64)]> {
+    scan(memory)
+        .into_iter()
+        .filter_map(|token| match token.kind {
+            TokenKind::Mul(l, r) => Some((l, r)),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn parse_handle_do(memory: &str) -> Vec<(i64, i64)> {
+    execute(&scan(memory), &InstructionSet::puzzle(), Reduction::SUM)
+        .steps
+        .into_iter()
+        .filter(|step| step.enabled)
+        .filter_map(|step| match step.token.kind {
+            TokenKind::Mul(l, r) => Some((l, r)),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn mul_sum(instructions: &[(i64, i64)]) -> i64 {
+    instructions.iter().map(|(l, r)| l * r).sum()
+}
+
+// pieces that look almost like instructions, but are not
+const CORRUPTED: [&str; 8] = [
+    "mul(4*",
+    "mul ( 2 , 4 )",
+    "mul[3,7]",
+    "?(12,34)",
+    "mul(6,9!",
+    "do_not_mul(",
+    "don't",
+    "do(",
+];
+
+const JUNK: &[u8] = b"!@#$%^&*()[]{}<>?/+-,:;' whyfromselectwhere";
+
+// Corrupted memory of about `len` characters with valid `mul`, `do()` and `don't()` instructions
+// between junk, broken instructions and some line breaks.
+pub fn generate(rng: &mut Rng, len: usize) -> String {
+    let mut memory = String::with_capacity(len + 16);
+    while memory.len() < len {
+        match rng.below(20) {
+            0..=5 => {
+                let _ = write!(memory, "mul({},{})", rng.range(1..1000), rng.range(1..1000));
+            }
+            6 => memory.push_str("do()"),
+            7 => memory.push_str("don't()"),
+            8 | 9 => {
+                let corrupted: &&str = rng.choose(&CORRUPTED);
+                memory.push_str(corrupted);
+            }
+            10 if rng.chance(1, 20) => memory.push('\n'),
+            _ => memory.push(*rng.choose(JUNK) as char),
+        }
+    }
+    memory.push('\n');
+    memory
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_ignore_corrupted_works_for_example() {
+        // when
+        let instructions = parse_ignore_corrupted(
+            "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))",
+        );
+
+        // then
+        let expected: &[(i64, i64)] = &[(2, 4), (5, 5), (11, 8), (8, 5)];
+        let actual: &[(i64, i64)] = &instructions;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_handle_do_works_for_example() {
+        // when
+        let instructions = parse_handle_do(
+            "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+        );
+
+        // then
+        let expected: &[(i64, i64)] = &[(2, 4), (8, 5)];
+        let actual: &[(i64, i64)] = &instructions;
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,259 @@
+// What an instruction does. The built-in instructions wrap around on overflow, as deeply nested
+// ones easily leave the range of `i64`.
+#[derive(Copy, Clone)]
+pub enum Semantics {
+    // computes a value from the arguments, which is reduced into the result
+    Value(fn(&[i64]) -> i64),
+    // enables (`true`) or disables the value instructions that follow
+    Toggle(bool),
+    // enables the value instructions that follow if the arguments pass, otherwise disables them
+    ToggleIf(fn(&[i64]) -> bool),
+}
+
+#[derive(Clone)]
+pub struct Instruction {
+    pub name: Box<str>,
+    pub arity: usize,
+    pub semantics: Semantics,
+    // one of the instructions of the puzzle, as the puzzle defines it
+    puzzle: bool,
+}
+
+// The instructions that are recognised in the memory, written as `name(a,b,..)`. Arguments are
+// numbers of 1 to 3 digits or, if nesting is allowed, value instructions.
+#[derive(Clone, Default)]
+pub struct InstructionSet {
+    instructions: Vec<Instruction>,
+    nested: bool,
+}
+
+// Instructions nested deeper than this are not recognised. Without a limit a long run of `mul(`
+// overflows the stack, and as the scan tries every position, it takes quadratic time.
+const MAX_DEPTH: usize = 32;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Argument {
+    Literal(i64),
+    Call(Call),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Call {
+    // index into the instruction set
+    pub instruction: usize,
+    pub arguments: Box<[Argument]>,
+}
+
+impl InstructionSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // `mul`, `do()` and `don't()` of the puzzle
+    pub fn puzzle() -> Self {
+        let mut instructions = Self::new()
+            .define(
+                "mul",
+                2,
+                Semantics::Value(|args| args[0].wrapping_mul(args[1])),
+            )
+            .define("do", 0, Semantics::Toggle(true))
+            .define("don't", 0, Semantics::Toggle(false));
+        for instruction in &mut instructions.instructions {
+            instruction.puzzle = true;
+        }
+        instructions
+    }
+
+    // Adds an instruction, or replaces the one of the same name.
+    pub fn define(mut self, name: &str, arity: usize, semantics: Semantics) -> Self {
+        let instruction = Instruction {
+            name: name.into(),
+            arity,
+            semantics,
+            puzzle: false,
+        };
+        match self.instructions.iter().position(|i| *i.name == *name) {
+            Some(i) => self.instructions[i] = instruction,
+            None => self.instructions.push(instruction),
+        }
+        self
+    }
+
+    // Whether value instructions can be arguments, like `mul(mul(1,2),3)`. Otherwise only the
+    // inner instruction is recognised there.
+    pub fn nested(mut self, nested: bool) -> Self {
+        self.nested = nested;
+        self
+    }
+
+    pub fn get(&self, instruction: usize) -> &Instruction {
+        &self.instructions[instruction]
+    }
+
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.instructions.iter().position(|i| *i.name == *name)
+    }
+
+    // whether the instruction is the one of the puzzle of that name, not a replacement
+    pub fn is_puzzle(&self, instruction: usize) -> bool {
+        self.instructions[instruction].puzzle
+    }
+
+    // The instruction at the start of `bytes` and its length.
+    pub fn parse_at(&self, bytes: &[u8]) -> Option<(Call, usize)> {
+        self.parse_nested_at(bytes, 0)
+    }
+
+    // `depth` is the number of instructions this one is an argument of
+    fn parse_nested_at(&self, bytes: &[u8], depth: usize) -> Option<(Call, usize)> {
+        self.instructions
+            .iter()
+            .enumerate()
+            .find_map(|(i, instruction)| {
+                let rest = bytes.strip_prefix(instruction.name.as_bytes())?;
+                let mut rest = rest.strip_prefix(b"(")?;
+                let mut arguments: Vec<Argument> = Vec::with_capacity(instruction.arity);
+                for a in 0..instruction.arity {
+                    if a > 0 {
+                        rest = rest.strip_prefix(b",")?;
+                    }
+                    let (argument, len) = self.argument(rest, depth)?;
+                    arguments.push(argument);
+                    rest = &rest[len..];
+                }
+                rest.strip_prefix(b")")?;
+                let call = Call {
+                    instruction: i,
+                    arguments: arguments.into_boxed_slice(),
+                };
+                Some((call, bytes.len() - rest.len() + 1))
+            })
+    }
+
+    fn argument(&self, bytes: &[u8], depth: usize) -> Option<(Argument, usize)> {
+        if let Some((value, len)) = literal(bytes) {
+            return Some((Argument::Literal(value), len));
+        }
+        if !self.nested || depth >= MAX_DEPTH {
+            return None;
+        }
+        let (call, len) = self.parse_nested_at(bytes, depth + 1)?;
+        match self.get(call.instruction).semantics {
+            Semantics::Value(_) => Some((Argument::Call(call), len)),
+            Semantics::Toggle(_) | Semantics::ToggleIf(_) => None,
+        }
+    }
+
+    fn arguments(&self, call: &Call) -> Box<[i64]> {
+        call.arguments
+            .iter()
+            .map(|argument| match argument {
+                Argument::Literal(value) => *value,
+                Argument::Call(call) => self.value(call).unwrap_or(0),
+            })
+            .collect()
+    }
+
+    // the value of a value instruction
+    pub fn value(&self, call: &Call) -> Option<i64> {
+        match self.get(call.instruction).semantics {
+            Semantics::Value(f) => Some(f(&self.arguments(call))),
+            Semantics::Toggle(_) | Semantics::ToggleIf(_) => None,
+        }
+    }
+
+    // Whether a toggle instruction enables the value instructions that follow.
+    pub fn toggle(&self, call: &Call) -> Option<bool> {
+        match self.get(call.instruction).semantics {
+            Semantics::Value(_) => None,
+            Semantics::Toggle(on) => Some(on),
+            Semantics::ToggleIf(condition) => Some(condition(&self.arguments(call))),
+        }
+    }
+}
+
+// 1 to 3 digits
+fn literal(bytes: &[u8]) -> Option<(i64, usize)> {
+    let digits = bytes
+        .iter()
+        .take(4)
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    if !(1..=3).contains(&digits) {
+        return None;
+    }
+    let value = bytes[..digits]
+        .iter()
+        .fold(0, |value, digit| value * 10 + (digit - b'0') as i64);
+    Some((value, digits))
+}
+
+// How the values of the value instructions are combined, starting with `initial`.
+#[derive(Copy, Clone)]
+pub struct Reduction {
+    pub initial: i64,
+    pub combine: fn(i64, i64) -> i64,
+}
+
+impl Reduction {
+    pub const SUM: Reduction = Reduction {
+        initial: 0,
+        combine: i64::wrapping_add,
+    };
+    pub const PRODUCT: Reduction = Reduction {
+        initial: 1,
+        combine: i64::wrapping_mul,
+    };
+    pub const MIN: Reduction = Reduction {
+        initial: i64::MAX,
+        combine: i64::min,
+    };
+    pub const MAX: Reduction = Reduction {
+        initial: i64::MIN,
+        combine: i64::max,
+    };
+
+    pub fn parse(name: &str) -> Option<Reduction> {
+        match name {
+            "sum" => Some(Reduction::SUM),
+            "product" => Some(Reduction::PRODUCT),
+            "min" => Some(Reduction::MIN),
+            "max" => Some(Reduction::MAX),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scanner::{scan_with, TokenKind};
+
+    #[test]
+    fn parse_at_limits_the_nesting_depth() {
+        // given
+        let instructions = InstructionSet::puzzle().nested(true);
+        let deep = format!("{}1{}", "mul(2,".repeat(40), ")".repeat(40));
+        let unclosed = "mul(".repeat(20_000);
+
+        // when
+        let calls: Vec<usize> = scan_with(&deep, &instructions)
+            .into_iter()
+            .filter(|token| token.kind != TokenKind::Corrupted)
+            .map(|token| token.span.start)
+            .collect();
+
+        // then
+        assert_eq!(calls, [6 * (40 - MAX_DEPTH - 1)]);
+        assert!(scan_with(&unclosed, &instructions)
+            .iter()
+            .all(|token| token.kind == TokenKind::Corrupted));
+    }
+
+    #[test]
+    fn reductions_have_names() {
+        assert_eq!(Reduction::parse("product").map(|r| r.initial), Some(1));
+        assert!(Reduction::parse("average").is_none());
+    }
+}
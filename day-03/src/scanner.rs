@@ -0,0 +1,142 @@
+use crate::registry::{Argument, Call, InstructionSet};
+use std::ops::Range;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TokenKind {
+    Mul(i64, i64),
+    Do,
+    Dont,
+    // any other instruction of the instruction set, like nested or newly defined ones
+    Call(Call),
+    // everything between the instructions
+    Corrupted,
+}
+
+// A piece of the memory with the byte offsets it spans.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Range<usize>,
+}
+
+// Splits the memory into the instructions of the puzzle and the corrupted spans between them.
+pub fn scan(memory: &str) -> Vec<Token> {
+    scan_with(memory, &InstructionSet::puzzle())
+}
+
+// Splits the memory into instructions and the corrupted spans between them. Every byte of the
+// memory belongs to exactly one token.
+pub fn scan_with(memory: &str, instructions: &InstructionSet) -> Vec<Token> {
+    let bytes = memory.as_bytes();
+    let mut tokens: Vec<Token> = Vec::with_capacity(memory.len() / 8);
+    let mut corrupted_start: Option<usize> = None;
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let Some((call, len)) = instructions.parse_at(&bytes[pos..]) else {
+            corrupted_start.get_or_insert(pos);
+            pos += 1;
+            continue;
+        };
+        if let Some(start) = corrupted_start.take() {
+            tokens.push(Token {
+                kind: TokenKind::Corrupted,
+                span: start..pos,
+            });
+        }
+        tokens.push(Token {
+            kind: token_kind(instructions, call),
+            span: pos..pos + len,
+        });
+        pos += len;
+    }
+    if let Some(start) = corrupted_start {
+        tokens.push(Token {
+            kind: TokenKind::Corrupted,
+            span: start..bytes.len(),
+        });
+    }
+    tokens
+}
+
+// The instructions of the puzzle with literal arguments get tokens of their own.
+fn token_kind(instructions: &InstructionSet, call: Call) -> TokenKind {
+    if !instructions.is_puzzle(call.instruction) {
+        return TokenKind::Call(call);
+    }
+    match (
+        &*instructions.get(call.instruction).name,
+        &call.arguments[..],
+    ) {
+        ("mul", [Argument::Literal(l), Argument::Literal(r)]) => TokenKind::Mul(*l, *r),
+        ("do", _) => TokenKind::Do,
+        ("don't", _) => TokenKind::Dont,
+        _ => TokenKind::Call(call),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scan_finds_instructions_and_corrupted_spans() {
+        // when
+        let tokens = scan("xmul(2,4)do()mul(1234,5)mul(1,2)don't()");
+
+        // then
+        let expected = [
+            (TokenKind::Corrupted, 0..1),
+            (TokenKind::Mul(2, 4), 1..9),
+            (TokenKind::Do, 9..13),
+            (TokenKind::Corrupted, 13..24),
+            (TokenKind::Mul(1, 2), 24..32),
+            (TokenKind::Dont, 32..39),
+        ]
+        .map(|(kind, span)| Token { kind, span });
+        assert_eq!(tokens, expected);
+    }
+
+    #[test]
+    fn scan_requires_one_to_three_digits() {
+        // when
+        let kinds: Vec<TokenKind> = scan("mul(,1)mul(1,)mul(999,1)mul(1,1000)mul(-1,2)")
+            .into_iter()
+            .map(|token| token.kind)
+            .collect();
+
+        // then
+        assert_eq!(
+            kinds,
+            [
+                TokenKind::Corrupted,
+                TokenKind::Mul(999, 1),
+                TokenKind::Corrupted
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_with_emits_calls_for_other_instructions() {
+        // given
+        let instructions = InstructionSet::puzzle()
+            .define("neg", 1, crate::registry::Semantics::Value(|args| -args[0]))
+            .nested(true);
+
+        // when
+        let tokens = scan_with("mul(neg(2),3)neg(4)do()", &instructions);
+
+        // then
+        let calls: Vec<&str> = tokens
+            .iter()
+            .map(|token| match &token.kind {
+                TokenKind::Call(call) => instructions.get(call.instruction).name.as_ref(),
+                TokenKind::Mul(..) => "Mul",
+                TokenKind::Do => "Do",
+                TokenKind::Dont => "Dont",
+                TokenKind::Corrupted => "Corrupted",
+            })
+            .collect();
+        assert_eq!(calls, ["mul", "neg", "Do"]);
+        assert_eq!(tokens[0].span, 0..13);
+    }
+}
@@ -0,0 +1,161 @@
+use common::random::{GenOptions, Rng};
+use common::{Answer, ParseError, Picture, Solution};
+use grid::{Grid, NEIGHBOURS_8};
+use template::Template;
+use word_search::WordSearch;
+
+pub mod template;
+pub mod word_search;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input<'a> = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        make_grid(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(WordSearch::new(&["XMAS"]).find(input).len().into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let x_mas = Template::parse("M.S/.A./M.S").map_err(|e| e.to_string())?;
+        Ok(x_mas.count(input).into())
+    }
+
+    fn picture(input: &Self::Input<'_>) -> Result<Option<Picture>, String> {
+        let search = WordSearch::new(&["XMAS"]);
+        Ok(Some(search.picture(input, &search.find(input))))
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(140)))
+    }
+}
+
+pub fn make_grid(input: &str) -> Result<Grid<u8>, ParseError> {
+    // we compare single bytes later, so we assume ascii input here to make it easier
+    Grid::parse(input, "an ASCII character", |c, _| {
+        c.is_ascii().then_some(c as u8)
+    })
+}
+
+// A hex grid for the word search, e.g. for `Tiling::Hex`. It is written with spaces between the
+// letters and every row indented half a letter more than the one above, so whitespace is ignored.
+pub fn make_hex_grid(input: &str) -> Result<Grid<u8>, ParseError> {
+    let mut width: Option<usize> = None;
+    let mut cells: Vec<u8> = Vec::with_capacity(input.len() / 2);
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let row_start = cells.len();
+        for (i, c) in line.char_indices().filter(|(_, c)| !c.is_whitespace()) {
+            if !c.is_ascii() {
+                return Err(ParseError::at_char(line, i, "an ASCII character"));
+            }
+            cells.push(c as u8);
+        }
+        let row_width = cells.len() - row_start;
+        if *width.get_or_insert(row_width) != row_width {
+            return Err(ParseError::new(
+                line,
+                format!("a row of {} letters", width.unwrap_or_default()),
+            ));
+        }
+    }
+    let width = width.ok_or_else(|| ParseError::after(input, "at least one row"))?;
+    let height = cells.len() / width.max(1);
+    Ok(Grid::new(width, height, cells))
+}
+
+pub fn count_non_palindrome(grid: &Grid<u8>, word: &[u8]) -> u32 {
+    if word.is_empty() {
+        return 0;
+    }
+    grid.iter()
+        .filter(|(_, c)| **c == word[0])
+        .map(|(pos, _)| {
+            // horizontal, vertical and diagonal, forwards and backwards
+            NEIGHBOURS_8
+                .iter()
+                .filter(|dir| grid.ray(pos, **dir).take(word.len()).eq(word))
+                .count() as u32
+        })
+        .sum()
+}
+
+pub fn count_x_mas(grid: &Grid<u8>) -> usize {
+    let is_mas = |a: Option<&u8>, b: Option<&u8>| {
+        matches!((a, b), (Some(b'M'), Some(b'S')) | (Some(b'S'), Some(b'M')))
+    };
+    grid.iter()
+        .filter(|(_, c)| **c == b'A')
+        .filter(|((x, y), _)| {
+            let (x, y) = (*x as isize, *y as isize);
+            is_mas(
+                grid.get_signed((x - 1, y - 1)),
+                grid.get_signed((x + 1, y + 1)),
+            ) && is_mas(
+                grid.get_signed((x + 1, y - 1)),
+                grid.get_signed((x - 1, y + 1)),
+            )
+        })
+        .count()
+}
+
+// A square of the letters X, M, A and S. Every letter is equally likely, which already gives plenty
+// of matches.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(*rng.choose(b"XMAS") as char);
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = r#"MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+"#;
+
+    #[test]
+    fn count_non_palindrome_works_for_example() {
+        // given
+        let grid = make_grid(INPUT).expect("expected well-formed input");
+        let search_word = b"XMAS";
+
+        // when
+        let count = count_non_palindrome(&grid, search_word);
+
+        // then
+        assert_eq!(count, 18);
+    }
+
+    #[test]
+    fn count_x_mas_works_for_example() {
+        // given
+        let grid = make_grid(INPUT).expect("expected well-formed input");
+
+        // when
+        let count = count_x_mas(&grid);
+
+        // then
+        assert_eq!(count, 9);
+    }
+}
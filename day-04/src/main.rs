@@ -1,202 +1,99 @@
+use common::{Cell, Color};
+use day_04::template::Template;
+use day_04::word_search::{Geometry, Tiling, WordSearch};
+use day_04::{make_grid, make_hex_grid};
+use grid::Grid;
 use std::env;
-use std::fs::read_to_string;
-use std::path::Path;
+use std::fs;
+use std::io::{self, Read};
 
 fn main() -> Result<(), String> {
-    let filename = env::args()
-        .nth(1)
-        .ok_or_else(|| "No file name given.".to_owned())?;
-    let content = read_to_string(Path::new(&filename)).map_err(|e| e.to_string())?;
-    let (width, height, grid) = make_grid(&content)?;
-
-    let xmas_count = count_non_palindrome(&grid, width, height, b"XMAS");
-    println!("{xmas_count} occurences of 'XMAS'");
-
-    // I would probably kill anyone who names variables this way in production code
-    let x_mas_count = count_x_mas(&grid, width, height);
-    println!("{x_mas_count} occurences of an 'X' MAS");
-
-    Ok(())
-}
-
-fn make_grid(input: &str) -> Result<(usize, usize, Box<[u8]>), String> {
-    // the grid indices we use later require uniform char length. We assume ascii input here to
-    // make it easier
-    if !input.is_ascii() {
-        return Err("input is not all ASCII".to_string());
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "--words") {
+        return find_words(&args[1..]);
     }
-    let height = input.lines().count();
-    let mut lines = input.lines();
-    let width = lines.next().unwrap_or("").len();
-    if lines.any(|line| line.len() != width) {
-        return Err("not all input lines are equally long!".to_string());
+    if args.first().is_some_and(|arg| arg == "--template") {
+        return find_template(&args[1..]);
     }
-    let grid = input
-        .as_bytes()
-        .iter()
-        .filter(|c| **c != b'\n')
-        .copied()
-        .collect();
-    Ok((width, height, grid))
+    common::main::<day_04::Day04>()
 }
 
-fn count_non_palindrome(input: &[u8], width: usize, height: usize, word: &[u8]) -> u32 {
-    if word.is_empty() {
-        return 0;
+// `--words <word,word,..> [--torus] [--hex] [file]`: every match as word, start and direction,
+// then the grid with the matches highlighted
+fn find_words(args: &[String]) -> Result<(), String> {
+    let mut geometry = Geometry::default();
+    let mut positional: Vec<&String> = Vec::with_capacity(2);
+    for arg in args {
+        match arg.as_str() {
+            "--torus" => geometry.wrap = true,
+            "--hex" => geometry.tiling = Tiling::Hex,
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{arg}'")),
+            _ => positional.push(arg),
+        }
     }
-    input
-        .iter()
-        .enumerate()
-        .filter(|(_, c)| **c == word[0])
-        .map(|(i, _)| {
-            let x = i % width;
-            let y = i / width;
-
-            let mut count: u32 = 0;
-
-            // backwards horizontal
-            if x + 1 >= word.len()
-                && word
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .all(|(dx, c)| c == input[y * width + x - dx])
-            {
-                count += 1;
-            }
-            // forwards horizontal
-            if width >= word.len() + x
-                && word
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .all(|(dx, c)| c == input[y * width + x + dx])
-            {
-                count += 1;
-            }
-            // backwards vertical
-            if y + 1 >= word.len()
-                && word
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .all(|(dy, c)| c == input[(y - dy) * width + x])
-            {
-                count += 1;
-            }
-            // forwards vertical
-            if height >= word.len() + y
-                && word
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .all(|(dy, c)| c == input[(y + dy) * width + x])
-            {
-                count += 1;
-            }
-            // diagonal up left
-            if x + 1 >= word.len()
-                && y + 1 >= word.len()
-                && word
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .all(|(d, c)| c == input[(y - d) * width + x - d])
-            {
-                count += 1;
-            }
-            // diagonal up right
-            if width >= word.len() + x
-                && y + 1 >= word.len()
-                && word
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .all(|(d, c)| c == input[(y - d) * width + x + d])
-            {
-                count += 1;
-            }
-            // diagonal down right
-            if width >= word.len() + x
-                && height >= word.len() + y
-                && word
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .all(|(d, c)| c == input[(y + d) * width + x + d])
-            {
-                count += 1;
-            }
-            // diagonal down left
-            if x + 1 >= word.len()
-                && height >= word.len() + y
-                && word
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .all(|(d, c)| c == input[(y + d) * width + x - d])
-            {
-                count += 1;
-            }
-
-            count
-        })
-        .sum()
-}
+    let (words, input) = match positional[..] {
+        [words] => (words, None),
+        [words, path] => (words, Some(path)),
+        [] => return Err("expected a comma separated list of words after '--words'".to_owned()),
+        [_, _, arg, ..] => return Err(format!("unexpected argument '{arg}'")),
+    };
+    let words: Vec<&str> = words.split(',').filter(|w| !w.is_empty()).collect();
+    let text = read_input(input)?;
+    let grid = match geometry.tiling {
+        Tiling::Square => make_grid(&text),
+        Tiling::Hex => make_hex_grid(&text),
+    }
+    .map_err(|e| e.locate(&text).with_day(4).to_string())?;
 
-fn count_x_mas(grid: &[u8], width: usize, height: usize) -> usize {
-    grid.iter()
-        .enumerate()
-        .map(|(i, c)| (i % width, i / width, *c))
-        .filter(|(x, y, c)| *c == b'A' && *x > 0 && *y > 0 && x + 1 < width && y + 1 < height)
-        .filter(|(x, y, _)| {
-            (grid[(y - 1) * width + x - 1] == b'M' && grid[(y + 1) * width + x + 1] == b'S'
-                || grid[(y - 1) * width + x - 1] == b'S' && grid[(y + 1) * width + x + 1] == b'M')
-                && (grid[(y - 1) * width + x + 1] == b'M' && grid[(y + 1) * width + x - 1] == b'S'
-                    || grid[(y - 1) * width + x + 1] == b'S'
-                        && grid[(y + 1) * width + x - 1] == b'M')
-        })
-        .count()
+    let search = WordSearch::new(&words).with_geometry(geometry);
+    let matches = search.find(&grid);
+    for m in &matches {
+        let ((x, y), (dx, dy)) = (m.start, m.direction);
+        println!("{} at {x},{y} towards {dx},{dy}", words[m.word]);
+    }
+    println!("{} matches", matches.len());
+    print!("{}", search.picture(&grid, &matches).to_ansi());
+    Ok(())
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    static INPUT: &str = r#"MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX
-"#;
-
-    #[test]
-    fn count_non_palindrome_works_for_example() {
-        // given
-        let (width, height, grid) = make_grid(INPUT).expect("expected well-formed input");
-        let search_word = b"XMAS";
-
-        // when
-        let count = count_non_palindrome(&grid, width, height, search_word);
-
-        // then
-        assert_eq!(count, 18);
+// `--template <rows separated by /> [file]`: the top left corner of every place the template fits
+// in any rotation or reflection, then the grid with the matches highlighted
+fn find_template(args: &[String]) -> Result<(), String> {
+    let (pattern, input) = match args {
+        [pattern] => (pattern, None),
+        [pattern, path] => (pattern, Some(path)),
+        [] => return Err("expected a template like 'M.S/.A./M.S' after '--template'".to_owned()),
+        [_, _, arg, ..] => return Err(format!("unexpected argument '{arg}'")),
+    };
+    let template = Template::parse(pattern).map_err(|e| e.to_string())?;
+    let grid = read_grid(input)?;
+
+    let matches = template.find(&grid);
+    for m in &matches {
+        let (x, y) = m.start;
+        println!("variant {} at {x},{y}", m.variant + 1);
     }
+    println!("{} matches", matches.len());
+    let mut picture = grid.picture(|c| Cell::new(*c as char).fg(Color::DARK_GRAY));
+    picture.highlight(matches.iter().flat_map(|m| template.cells(m)), Color::RED);
+    print!("{}", picture.to_ansi());
+    Ok(())
+}
 
-    #[test]
-    fn count_x_mas_works_for_example() {
-        // given
-        let (width, height, grid) = make_grid(INPUT).expect("expected well-formed input");
-
-        // when
-        let count = count_x_mas(&grid, width, height);
+fn read_grid(input: Option<&String>) -> Result<Grid<u8>, String> {
+    let text = read_input(input)?;
+    make_grid(&text).map_err(|e| e.locate(&text).with_day(4).to_string())
+}
 
-        // then
-        assert_eq!(count, 9);
+fn read_input(input: Option<&String>) -> Result<String, String> {
+    match input.filter(|path| *path != "-") {
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("unable to read '{path}': {e}")),
+        None => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("unable to read the input: {e}"))?;
+            Ok(text)
+        }
     }
 }
@@ -0,0 +1,230 @@
+use common::ParseError;
+use grid::{Grid, Pos};
+use std::collections::HashSet;
+
+// A small grid of letters to look for, with `.` for tiles that can hold any letter. It is found
+// rotated and mirrored as well.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Template {
+    // the distinct rotations and reflections, the template itself first
+    variants: Box<[Grid<Option<u8>>]>,
+}
+
+// A place where a variant of the template fits, `start` is its top left corner.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TemplateMatch {
+    pub variant: usize,
+    pub start: Pos,
+}
+
+impl Template {
+    // Rows separated by `/` or line breaks, e.g. `M.S/.A./M.S` for the X-MAS of part 2. Spaces
+    // around a row are ignored, but not inside of it.
+    pub fn parse(pattern: &str) -> Result<Template, ParseError> {
+        let rows: Vec<&str> = pattern
+            .split(['/', '\n'])
+            .map(str::trim)
+            .filter(|row| !row.is_empty())
+            .collect();
+        let width = rows
+            .first()
+            .ok_or_else(|| ParseError::after(pattern, "at least one row").locate(pattern))?
+            .chars()
+            .count();
+        let mut cells: Vec<Option<u8>> = Vec::with_capacity(width * rows.len());
+        for row in &rows {
+            for (i, c) in row.char_indices() {
+                let cell = match c {
+                    '.' => None,
+                    c if c.is_whitespace() => {
+                        let e = ParseError::at_char(row, i, "a letter or '.', not a space");
+                        return Err(e.locate(pattern));
+                    }
+                    c if c.is_ascii() => Some(c as u8),
+                    _ => {
+                        let e = ParseError::at_char(row, i, "an ASCII letter or '.'");
+                        return Err(e.locate(pattern));
+                    }
+                };
+                cells.push(cell);
+            }
+            if row.chars().count() != width {
+                let e = ParseError::new(
+                    row,
+                    format!("a row of {width} letters or '.' like the first one"),
+                );
+                return Err(e.locate(pattern));
+            }
+        }
+        // it would fit everywhere, and matches are told apart by the letters they cover
+        if cells.iter().all(Option::is_none) {
+            let e = ParseError::new(rows[0], "at least one letter, not only '.'");
+            return Err(e.locate(pattern));
+        }
+        Ok(Template::new(Grid::new(width, rows.len(), cells)))
+    }
+
+    pub fn new(template: Grid<Option<u8>>) -> Template {
+        assert!(
+            template.iter().any(|(_, letter)| letter.is_some()),
+            "a template needs at least one letter"
+        );
+        let mut variants: Vec<Grid<Option<u8>>> = Vec::with_capacity(8);
+        let mut variant = template;
+        for _ in 0..4 {
+            for candidate in [variant.clone(), mirror(&variant)] {
+                if !variants.contains(&candidate) {
+                    variants.push(candidate);
+                }
+            }
+            variant = rotate(&variant);
+        }
+        Template {
+            variants: variants.into_boxed_slice(),
+        }
+    }
+
+    pub fn variants(&self) -> &[Grid<Option<u8>>] {
+        &self.variants
+    }
+
+    // Every place where one of the variants fits. Variants that cover the same tiles at the same
+    // place (like the two diagonals of an X read in either order) are the same match, only the
+    // first of them is reported.
+    pub fn find(&self, grid: &Grid<u8>) -> Vec<TemplateMatch> {
+        let mut seen: HashSet<Box<[Pos]>> = HashSet::new();
+        let mut matches: Vec<TemplateMatch> = Vec::new();
+        for (variant, template) in self.variants.iter().enumerate() {
+            if template.width() > grid.width() || template.height() > grid.height() {
+                continue;
+            }
+            for y in 0..=grid.height() - template.height() {
+                for x in 0..=grid.width() - template.width() {
+                    let fits = template.iter().all(|((tx, ty), letter)| {
+                        letter.is_none_or(|letter| grid[(x + tx, y + ty)] == letter)
+                    });
+                    if !fits {
+                        continue;
+                    }
+                    let m = TemplateMatch {
+                        variant,
+                        start: (x, y),
+                    };
+                    if seen.insert(self.cells(&m).collect()) {
+                        matches.push(m);
+                    }
+                }
+            }
+        }
+        matches.sort_unstable_by_key(|m| (m.start.1, m.start.0, m.variant));
+        matches
+    }
+
+    pub fn count(&self, grid: &Grid<u8>) -> usize {
+        self.find(grid).len()
+    }
+
+    // the tiles of the grid covered by the letters (not the wildcards) of a match
+    pub fn cells<'a>(&'a self, m: &TemplateMatch) -> impl Iterator<Item = Pos> + 'a {
+        let (x, y) = m.start;
+        self.variants[m.variant]
+            .iter()
+            .filter(|(_, letter)| letter.is_some())
+            .map(move |((tx, ty), _)| (x + tx, y + ty))
+    }
+}
+
+// a quarter turn clockwise
+fn rotate(template: &Grid<Option<u8>>) -> Grid<Option<u8>> {
+    let height = template.height();
+    Grid::from_fn(height, template.width(), |(x, y)| {
+        template[(y, height - 1 - x)]
+    })
+}
+
+// left to right
+fn mirror(template: &Grid<Option<u8>>) -> Grid<Option<u8>> {
+    let width = template.width();
+    Grid::from_fn(width, template.height(), |(x, y)| {
+        template[(width - 1 - x, y)]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{count_x_mas, generate, make_grid};
+    use common::random::check_property;
+
+    #[test]
+    fn template_finds_the_x_mas() {
+        check_property(30, |rng| {
+            // given
+            let size = rng.range_usize(1..15);
+            let grid = make_grid(&generate(rng, size)).map_err(|e| e.to_string())?;
+            let template = Template::parse("M.S/.A./M.S").map_err(|e| e.to_string())?;
+
+            // when
+            let count = template.count(&grid);
+
+            // then
+            if count != count_x_mas(&grid) {
+                return Err(format!("expected {}, got {count}", count_x_mas(&grid)));
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn template_has_distinct_variants_only() {
+        // given
+        let x_mas = Template::parse("M.S / .A. / M.S").expect("expected valid template");
+        let line = Template::parse("XMAS").expect("expected valid template");
+        let l = Template::parse("X.\nXX").expect("expected valid template");
+
+        // then
+        assert_eq!(x_mas.variants().len(), 4);
+        assert_eq!(line.variants().len(), 4);
+        assert_eq!(l.variants().len(), 4);
+        assert_eq!(line.variants()[0], Grid::new(4, 1, b"XMAS".map(Some)));
+    }
+
+    #[test]
+    fn find_reports_positions_once() {
+        // given
+        let grid = make_grid("XMASAMX\n").expect("expected well-formed input");
+        let template = Template::parse("XMAS").expect("expected valid template");
+        let palindrome = Template::parse("A.A").expect("expected valid template");
+
+        // when
+        let matches = template.find(&grid);
+
+        // then
+        let starts: Vec<Pos> = matches.iter().map(|m| m.start).collect();
+        assert_eq!(starts, [(0, 0), (3, 0)]);
+        assert_eq!(
+            template.cells(&matches[1]).collect::<Vec<Pos>>(),
+            [(3, 0), (4, 0), (5, 0), (6, 0)]
+        );
+        assert_eq!(palindrome.count(&grid), 1);
+        assert!(Template::parse("XM/A").is_err());
+    }
+
+    #[test]
+    fn parse_locates_errors_in_the_pattern() {
+        // when
+        let space = Template::parse("M.S/.A./M S").expect_err("expected an error for the space");
+        let width = Template::parse("XM\nXMA").expect_err("expected an error for the width");
+        let letter = Template::parse(" M.S / .é.").expect_err("expected an error for 'é'");
+        let wildcards = Template::parse("../ ..").expect_err("expected an error for no letters");
+
+        // then
+        let location = |e: &ParseError| e.location.as_ref().map(|l| (l.line, l.column));
+        assert_eq!(location(&space), Some((1, 10)));
+        assert_eq!(space.expected, "a letter or '.', not a space");
+        assert_eq!(location(&width), Some((2, 1)));
+        assert_eq!(location(&letter), Some((1, 9)));
+        assert_eq!(location(&wildcards), Some((1, 1)));
+        assert_eq!(wildcards.expected, "at least one letter, not only '.'");
+    }
+}
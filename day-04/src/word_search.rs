@@ -0,0 +1,580 @@
+use common::{Cell, Color, Picture};
+use grid::{Grid, Offset, Pos, NEIGHBOURS_8};
+use std::collections::{HashSet, VecDeque};
+
+// A word found in the grid, read from `start` in `direction`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Match {
+    // index into the words of the search
+    pub word: usize,
+    pub start: Pos,
+    pub direction: Offset,
+}
+
+// The directions of a hex grid in axial coordinates: every row is shifted half a tile to the
+// right of the one above, so (x, y) touches (x, y - 1) and (x + 1, y - 1) above it and (x - 1, y + 1)
+// and (x, y + 1) below it.
+pub const HEX_NEIGHBOURS: [Offset; 6] = [(1, -1), (1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1)];
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Tiling {
+    // 8 directions, horizontally, vertically and diagonally
+    #[default]
+    Square,
+    // 6 directions, see `HEX_NEIGHBOURS`
+    Hex,
+}
+
+impl Tiling {
+    pub fn directions(self) -> &'static [Offset] {
+        match self {
+            Tiling::Square => &NEIGHBOURS_8,
+            Tiling::Hex => &HEX_NEIGHBOURS,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Geometry {
+    pub tiling: Tiling,
+    // whether the edges are joined to those opposite of them (a torus), so words can cross them
+    pub wrap: bool,
+}
+
+impl Geometry {
+    fn step(&self, grid: &Grid<u8>, (x, y): Pos, (dx, dy): Offset) -> Option<Pos> {
+        if !self.wrap {
+            return grid.offset((x, y), (dx, dy));
+        }
+        let (width, height) = (grid.width() as isize, grid.height() as isize);
+        Some((
+            (x as isize + dx).rem_euclid(width) as usize,
+            (y as isize + dy).rem_euclid(height) as usize,
+        ))
+    }
+}
+
+const ROOT: u32 = 0;
+
+// Searches many words at once with an Aho-Corasick automaton, which reads every line of the grid
+// once per direction, however many words there are.
+pub struct WordSearch {
+    words: Box<[Box<[u8]>]>,
+    // the bytes of the words are numbered from 1, all other bytes are 0, so the automaton only
+    // needs a transition for every byte that appears in a word
+    classes: Box<[u16; 256]>,
+    class_count: usize,
+    // the next state for every state and class, following the failure links where necessary
+    next: Box<[u32]>,
+    // the word that ends in a state
+    ends: Box<[Option<u32>]>,
+    // the next state along the failure links in which a word ends
+    dictionary: Box<[Option<u32>]>,
+    geometry: Geometry,
+}
+
+impl WordSearch {
+    pub fn new<W: AsRef<[u8]>>(words: &[W]) -> Self {
+        let words: Box<[Box<[u8]>]> = words.iter().map(|w| w.as_ref().into()).collect();
+        let mut classes = Box::new([0u16; 256]);
+        let mut class_count = 1;
+        for byte in words.iter().flat_map(|w| w.iter()) {
+            if classes[*byte as usize] == 0 {
+                classes[*byte as usize] = class_count as u16;
+                class_count += 1;
+            }
+        }
+
+        // the trie of the words, `u32::MAX` for missing children
+        let mut next: Vec<u32> = vec![u32::MAX; class_count];
+        let mut ends: Vec<Option<u32>> = vec![None];
+        for (i, word) in words.iter().enumerate() {
+            if word.is_empty() {
+                continue;
+            }
+            let mut state = ROOT;
+            for byte in word.iter() {
+                let edge = state as usize * class_count + classes[*byte as usize] as usize;
+                if next[edge] == u32::MAX {
+                    next[edge] = ends.len() as u32;
+                    next.extend(std::iter::repeat_n(u32::MAX, class_count));
+                    ends.push(None);
+                }
+                state = next[edge];
+            }
+            // a repeated word is only reported once, as the first one
+            ends[state as usize].get_or_insert(i as u32);
+        }
+
+        // breadth first, so the failure link of a state is done before its children
+        let mut failure: Vec<u32> = vec![ROOT; ends.len()];
+        let mut dictionary: Vec<Option<u32>> = vec![None; ends.len()];
+        let mut queue: VecDeque<u32> = VecDeque::with_capacity(ends.len());
+        for edge in &mut next[..class_count] {
+            match *edge {
+                u32::MAX => *edge = ROOT,
+                child => queue.push_back(child),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let fail = failure[state as usize] as usize;
+            for class in 0..class_count {
+                let edge = state as usize * class_count + class;
+                let fallback = next[fail * class_count + class];
+                match next[edge] {
+                    u32::MAX => next[edge] = fallback,
+                    child => {
+                        failure[child as usize] = fallback;
+                        dictionary[child as usize] = if ends[fallback as usize].is_some() {
+                            Some(fallback)
+                        } else {
+                            dictionary[fallback as usize]
+                        };
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+
+        WordSearch {
+            words,
+            classes,
+            class_count,
+            next: next.into_boxed_slice(),
+            ends: ends.into_boxed_slice(),
+            dictionary: dictionary.into_boxed_slice(),
+            geometry: Geometry::default(),
+        }
+    }
+
+    pub fn word(&self, word: usize) -> &[u8] {
+        &self.words[word]
+    }
+
+    pub fn words(&self) -> &[Box<[u8]>] {
+        &self.words
+    }
+
+    // The geometry the grid is searched in, the bounded square grid of the puzzle by default.
+    pub fn with_geometry(mut self, geometry: Geometry) -> Self {
+        self.geometry = geometry;
+        self
+    }
+
+    // All words in every direction of the geometry, forwards and backwards. A palindrome reads
+    // the same in both directions, so it is only reported in one of them.
+    pub fn find(&self, grid: &Grid<u8>) -> Vec<Match> {
+        let mut matches: Vec<Match> = Vec::new();
+        let mut line: Vec<Pos> = Vec::with_capacity(grid.width().max(grid.height()));
+        let directions = self.geometry.tiling.directions();
+        for direction in directions {
+            if self.geometry.wrap {
+                self.search_cycles(grid, *direction, &mut line, &mut matches);
+                continue;
+            }
+            let back = (-direction.0, -direction.1);
+            for start in grid.positions() {
+                if grid.offset(start, back).is_some() {
+                    continue;
+                }
+                line.clear();
+                line.extend(std::iter::successors(Some(start), |pos| {
+                    grid.offset(*pos, *direction)
+                }));
+                self.search_line(grid, &line, *direction, line.len(), &mut matches);
+            }
+        }
+        self.deduplicate(grid, &mut matches, directions);
+        matches
+    }
+
+    // On a torus every line in a direction returns to where it started. It is read once around
+    // and then as far again as the longest word, so words can cross the start as well.
+    fn search_cycles(
+        &self,
+        grid: &Grid<u8>,
+        direction: Offset,
+        line: &mut Vec<Pos>,
+        matches: &mut Vec<Match>,
+    ) {
+        let longest = self.words.iter().map(|w| w.len()).max().unwrap_or(0);
+        let mut visited: Grid<bool> = Grid::filled(grid.width(), grid.height(), false);
+        for start in grid.positions() {
+            if visited[start] {
+                continue;
+            }
+            line.clear();
+            let mut pos = start;
+            loop {
+                visited[pos] = true;
+                line.push(pos);
+                pos = self.geometry.step(grid, pos, direction).unwrap_or(start);
+                if pos == start {
+                    break;
+                }
+            }
+            let cycle = line.len();
+            for i in 0..longest.saturating_sub(1) {
+                line.push(line[i % cycle]);
+            }
+            self.search_line(grid, line, direction, cycle, matches);
+        }
+    }
+
+    // Reports the words that start at one of the first `starts` positions of the line.
+    fn search_line(
+        &self,
+        grid: &Grid<u8>,
+        line: &[Pos],
+        direction: Offset,
+        starts: usize,
+        matches: &mut Vec<Match>,
+    ) {
+        let mut state = ROOT;
+        for (i, pos) in line.iter().enumerate() {
+            let class = self.classes[grid[*pos] as usize] as usize;
+            state = self.next[state as usize * self.class_count + class];
+            let mut found = if self.ends[state as usize].is_some() {
+                Some(state)
+            } else {
+                self.dictionary[state as usize]
+            };
+            while let Some(end) = found {
+                let word = self.ends[end as usize].unwrap_or_default() as usize;
+                let start = i + 1 - self.words[word].len();
+                if start < starts {
+                    matches.push(Match {
+                        word,
+                        start: line[start],
+                        direction,
+                    });
+                }
+                found = self.dictionary[end as usize];
+            }
+        }
+    }
+
+    // A palindrome is found once in each of two opposite directions, and a single letter in every
+    // direction. On a torus that is 1 or 2 tiles across, different directions can also walk over
+    // the same tiles, so there a match is only kept once for the tiles it covers, in either order.
+    fn deduplicate(&self, grid: &Grid<u8>, matches: &mut Vec<Match>, directions: &[Offset]) {
+        let palindromes: Box<[bool]> = self
+            .words
+            .iter()
+            .map(|w| w.iter().eq(w.iter().rev()))
+            .collect();
+        matches.retain(|m| {
+            let (dx, dy) = m.direction;
+            match self.words[m.word].len() {
+                1 => m.direction == directions[0],
+                _ => !palindromes[m.word] || (dx, dy) > (-dx, -dy),
+            }
+        });
+        matches.sort_unstable_by_key(|m| (m.start.1, m.start.0, m.word, m.direction));
+        if self.geometry.wrap {
+            let mut seen: HashSet<(usize, Box<[Pos]>)> = HashSet::with_capacity(matches.len());
+            matches.retain(|m| seen.insert((m.word, self.covered(grid, m))));
+        }
+    }
+
+    // the positions of the letters of a match in the smaller of both orders
+    fn covered(&self, grid: &Grid<u8>, m: &Match) -> Box<[Pos]> {
+        let mut cells: Box<[Pos]> = self.cells(grid, m).collect();
+        if cells.iter().rev().lt(cells.iter()) {
+            cells.reverse();
+        }
+        cells
+    }
+
+    // the positions of the letters of a match
+    pub fn cells<'a>(&'a self, grid: &'a Grid<u8>, m: &Match) -> impl Iterator<Item = Pos> + 'a {
+        let direction = m.direction;
+        std::iter::successors(Some(m.start), move |pos| {
+            self.geometry.step(grid, *pos, direction)
+        })
+        .take(self.words[m.word].len())
+    }
+
+    // The grid in gray, with the letters of every match in the colour of its word. Hex grids
+    // are drawn with a space between the letters and every row half a letter further right.
+    pub fn picture(&self, grid: &Grid<u8>, matches: &[Match]) -> Picture {
+        const COLORS: [Color; 6] = [
+            Color::RED,
+            Color::GREEN,
+            Color::BLUE,
+            Color::ORANGE,
+            Color::CYAN,
+            Color::MAGENTA,
+        ];
+        let place = |(x, y): Pos| match self.geometry.tiling {
+            Tiling::Square => (x, y),
+            Tiling::Hex => (2 * x + y, y),
+        };
+        let (width, height) = match self.geometry.tiling {
+            Tiling::Square => (grid.width(), grid.height()),
+            Tiling::Hex => (
+                (2 * grid.width() + grid.height()).saturating_sub(2),
+                grid.height(),
+            ),
+        };
+        let mut picture = Picture::new(width, height);
+        for (pos, c) in grid.iter() {
+            picture.set(place(pos), Cell::new(*c as char).fg(Color::DARK_GRAY));
+        }
+        for m in matches {
+            let color = COLORS[m.word % COLORS.len()];
+            for pos in self.cells(grid, m) {
+                let cell = Cell::new(grid[pos] as char).fg(Color::WHITE).bg(color);
+                picture.set(place(pos), cell);
+            }
+        }
+        picture
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{count_non_palindrome, generate, make_grid, make_hex_grid};
+    use common::random::check_property;
+
+    static INPUT: &str = r#"MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+"#;
+
+    #[test]
+    fn find_reports_every_match() {
+        // given
+        let grid = make_grid(INPUT).expect("expected well-formed input");
+        let search = WordSearch::new(&["XMAS", "MAS", "AMX"]);
+
+        // when
+        let matches = search.find(&grid);
+
+        // then
+        let count = |word| matches.iter().filter(|m| m.word == word).count();
+        assert_eq!(count(0), 18);
+        assert_eq!(count(1), count_non_palindrome(&grid, b"MAS") as usize);
+        assert!(matches.contains(&Match {
+            word: 0,
+            start: (5, 0),
+            direction: (1, 0),
+        }));
+        assert!(matches.contains(&Match {
+            word: 2,
+            start: (4, 4),
+            direction: (1, 0),
+        }));
+    }
+
+    #[test]
+    fn find_agrees_with_searching_every_word_on_its_own() {
+        check_property(30, |rng| {
+            // given
+            let size = rng.range_usize(1..12);
+            let grid = make_grid(&generate(rng, size)).map_err(|e| e.to_string())?;
+            let words: Vec<Vec<u8>> = (0..rng.range_usize(1..6))
+                .map(|_| {
+                    let len = rng.range_usize(2..5);
+                    (0..len).map(|_| *rng.choose(b"XMAS")).collect()
+                })
+                .collect();
+
+            // when
+            let matches = WordSearch::new(&words).find(&grid);
+
+            // then
+            for (i, word) in words.iter().enumerate() {
+                let found = matches.iter().filter(|m| m.word == i).count();
+                let mut expected = count_non_palindrome(&grid, word) as usize;
+                if words[..i].contains(word) {
+                    expected = 0;
+                } else if word.iter().eq(word.iter().rev()) {
+                    expected /= 2;
+                }
+                if found != expected {
+                    return Err(format!("expected {expected} of {word:?}, found {found}"));
+                }
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn find_reports_palindromes_once() {
+        // given
+        let grid = make_grid("ABA\nXBX\nABA\n").expect("expected well-formed input");
+
+        // when
+        let matches = WordSearch::new(&["ABA", "B"]).find(&grid);
+
+        // then
+        assert_eq!(matches.iter().filter(|m| m.word == 0).count(), 4);
+        assert_eq!(matches.iter().filter(|m| m.word == 1).count(), 3);
+    }
+
+    #[test]
+    fn picture_highlights_the_matches() {
+        // given
+        let grid = make_grid("XMAS\nAAAA\n").expect("expected well-formed input");
+        let search = WordSearch::new(&["XMAS"]);
+
+        // when
+        let picture = search.picture(&grid, &search.find(&grid));
+
+        // then
+        assert_eq!(picture.get((3, 0)).and_then(|c| c.bg), Some(Color::RED));
+        assert_eq!(picture.get((3, 1)).and_then(|c| c.bg), None);
+    }
+
+    // the words found by walking from every tile in every direction, counting every walk over
+    // the same tiles once, in whichever order it goes over them
+    fn count_by_walking(grid: &Grid<u8>, word: &[u8], geometry: Geometry) -> usize {
+        let directions = geometry.tiling.directions();
+        let walks: HashSet<Vec<Pos>> = grid
+            .positions()
+            .flat_map(|pos| directions.iter().map(move |dir| (pos, *dir)))
+            .filter_map(|(start, dir)| {
+                let mut pos = Some(start);
+                let mut walk: Vec<Pos> = Vec::with_capacity(word.len());
+                for letter in word {
+                    match pos {
+                        Some(p) if grid[p] == *letter => {
+                            walk.push(p);
+                            pos = geometry.step(grid, p, dir);
+                        }
+                        _ => return None,
+                    }
+                }
+                let back: Vec<Pos> = walk.iter().rev().copied().collect();
+                Some(walk.min(back))
+            })
+            .collect();
+        walks.len()
+    }
+
+    #[test]
+    fn find_agrees_with_walking_in_every_geometry() {
+        check_property(60, |rng| {
+            // given
+            let (width, height) = (rng.range_usize(1..9), rng.range_usize(1..9));
+            let grid = Grid::from_fn(width, height, |_| *rng.choose(b"XMAS"));
+            let geometry = Geometry {
+                tiling: *rng.choose(&[Tiling::Square, Tiling::Hex]),
+                wrap: rng.chance(1, 2),
+            };
+            let words: Vec<Vec<u8>> = (0..rng.range_usize(1..6))
+                .map(|_| {
+                    let len = rng.range_usize(1..7);
+                    (0..len).map(|_| *rng.choose(b"XMAS")).collect()
+                })
+                .collect();
+
+            // when
+            let matches = WordSearch::new(&words).with_geometry(geometry).find(&grid);
+
+            // then
+            for (i, word) in words.iter().enumerate() {
+                let found = matches.iter().filter(|m| m.word == i).count();
+                let mut expected = count_by_walking(&grid, word, geometry);
+                if words[..i].contains(word) {
+                    expected = 0;
+                }
+                if found != expected {
+                    return Err(format!(
+                        "expected {expected} of {word:?} in {geometry:?}, found {found}"
+                    ));
+                }
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn find_crosses_the_edges_of_a_torus() {
+        // given
+        let grid = make_grid("ASXM\nAAAA\n").expect("expected well-formed input");
+        let search = WordSearch::new(&["XMAS"]).with_geometry(Geometry {
+            tiling: Tiling::Square,
+            wrap: true,
+        });
+
+        // when
+        let matches = search.find(&grid);
+
+        // then
+        assert_eq!(
+            matches,
+            [Match {
+                word: 0,
+                start: (2, 0),
+                direction: (1, 0),
+            }]
+        );
+        assert_eq!(
+            search.cells(&grid, &matches[0]).collect::<Vec<Pos>>(),
+            [(2, 0), (3, 0), (0, 0), (1, 0)]
+        );
+        assert!(WordSearch::new(&["XMAS"]).find(&grid).is_empty());
+    }
+
+    #[test]
+    fn find_reports_a_match_on_a_narrow_torus_once() {
+        // given
+        let grid = make_grid("XM\nAS\n").expect("expected well-formed input");
+        let search = WordSearch::new(&["XMXM", "XAXA"]).with_geometry(Geometry {
+            tiling: Tiling::Square,
+            wrap: true,
+        });
+
+        // when
+        let matches = search.find(&grid);
+
+        // then
+        assert_eq!(
+            matches,
+            [
+                Match {
+                    word: 0,
+                    start: (0, 0),
+                    direction: (-1, 0),
+                },
+                Match {
+                    word: 1,
+                    start: (0, 0),
+                    direction: (0, -1),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn find_uses_the_six_directions_of_a_hex_grid() {
+        // given
+        let grid = make_hex_grid("A B C\n D E F\n  G H I\n").expect("expected hex grid");
+        let search = WordSearch::new(&["CEG", "AEI", "BE"]).with_geometry(Geometry {
+            tiling: Tiling::Hex,
+            wrap: false,
+        });
+
+        // when
+        let matches = search.find(&grid);
+
+        // then
+        let found: Vec<(usize, Offset)> = matches.iter().map(|m| (m.word, m.direction)).collect();
+        assert_eq!(found, [(2, (0, 1)), (0, (-1, 1))]);
+        assert_eq!(
+            search.picture(&grid, &matches).to_string(),
+            "A B C  \n D E F \n  G H I\n"
+        );
+        assert!(make_hex_grid("A B\n C\n").is_err());
+    }
+}
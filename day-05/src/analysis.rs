@@ -0,0 +1,146 @@
+use crate::{Rules, Update};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+// Cycles in the rules mean that there is no order that follows all of them. The real inputs have
+// cycles over all pages, only the rules between the pages of a single update have to be free of
+// them.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Analysis {
+    // the shortest cycle over all pages
+    pub cycle: Option<Box<[u32]>>,
+    // the shortest cycle of every update that has one, by index of the update
+    pub update_cycles: Vec<(usize, Box<[u32]>)>,
+}
+
+pub fn analyse(rules: &Rules, updates: &[Update]) -> Analysis {
+    Analysis {
+        cycle: find_cycle(rules),
+        update_cycles: updates
+            .iter()
+            .enumerate()
+            .filter_map(|(i, update)| Some((i, find_cycle_in(rules, update)?)))
+            .collect(),
+    }
+}
+
+// The pages of a shortest cycle, each of them has to come before the next one and the last one
+// before the first one.
+pub fn find_cycle(rules: &Rules) -> Option<Box<[u32]>> {
+    shortest_cycle(rules, |_| true)
+}
+
+// like `find_cycle`, but only with the rules between the given pages
+pub fn find_cycle_in(rules: &Rules, pages: &[u32]) -> Option<Box<[u32]>> {
+    let pages: HashSet<u32> = pages.iter().copied().collect();
+    shortest_cycle(rules, |page| pages.contains(&page))
+}
+
+// A breadth first search from every page finds the shortest way back to it.
+fn shortest_cycle(rules: &Rules, included: impl Fn(u32) -> bool) -> Option<Box<[u32]>> {
+    let mut starts: Vec<u32> = rules.keys().copied().filter(|p| included(*p)).collect();
+    starts.sort_unstable();
+    let mut shortest: Option<Box<[u32]>> = None;
+    for start in starts {
+        let mut previous: HashMap<u32, u32> = HashMap::new();
+        let mut queue: VecDeque<u32> = VecDeque::from([start]);
+        let mut last: Option<u32> = None;
+        'search: while let Some(page) = queue.pop_front() {
+            for later in rules.get(&page).into_iter().flatten() {
+                if *later == start {
+                    last = Some(page);
+                    break 'search;
+                }
+                if included(*later) && !previous.contains_key(later) {
+                    previous.insert(*later, page);
+                    queue.push_back(*later);
+                }
+            }
+        }
+        let Some(last) = last else {
+            continue;
+        };
+        let mut cycle: Vec<u32> = vec![last];
+        while let Some(page) = previous.get(&cycle[cycle.len() - 1]) {
+            cycle.push(*page);
+        }
+        if cycle[cycle.len() - 1] != start {
+            cycle.push(start);
+        }
+        cycle.reverse();
+        if shortest.as_ref().is_none_or(|s| cycle.len() < s.len()) {
+            shortest = Some(cycle.into_boxed_slice());
+        }
+    }
+    shortest
+}
+
+pub fn format_cycle(cycle: &[u32]) -> String {
+    let pages: Vec<String> = cycle
+        .iter()
+        .chain(cycle.first())
+        .map(|page| page.to_string())
+        .collect();
+    pages.join(" -> ")
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.cycle {
+            Some(cycle) => writeln!(f, "shortest cycle of all rules: {}", format_cycle(cycle))?,
+            None => writeln!(f, "the rules have no cycle")?,
+        }
+        if self.update_cycles.is_empty() {
+            writeln!(f, "the rules for the pages of every update have no cycle")?;
+        }
+        for (i, cycle) in &self.update_cycles {
+            writeln!(
+                f,
+                "update {} has no valid order: {}",
+                i + 1,
+                format_cycle(cycle)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn find_cycle_finds_the_shortest_cycle() {
+        // given
+        let (rules, updates) = parse("1|2\n2|3\n3|4\n4|1\n3|2\n2|4\n5|5\n\n1,2,4\n2,3\n4,1,3\n")
+            .expect("expected input");
+
+        // when
+        let analysis = analyse(&rules, &updates);
+
+        // then
+        assert_eq!(analysis.cycle.as_deref(), Some(&[5][..]));
+        assert_eq!(
+            analysis.update_cycles,
+            vec![(0, [1, 2, 4].into()), (1, [2, 3].into())]
+        );
+        assert_eq!(
+            analysis.to_string(),
+            "shortest cycle of all rules: 5 -> 5
+update 1 has no valid order: 1 -> 2 -> 4 -> 1
+update 2 has no valid order: 2 -> 3 -> 2
+"
+        );
+    }
+
+    #[test]
+    fn find_cycle_finds_nothing_in_a_consistent_order() {
+        // given
+        let (rules, updates) = parse("1|2\n1|3\n2|3\n\n3,1,2\n").expect("expected input");
+
+        // then
+        assert_eq!(find_cycle(&rules), None);
+        assert_eq!(find_cycle_in(&rules, &updates[0]), None);
+    }
+}
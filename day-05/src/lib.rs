@@ -0,0 +1,353 @@
+use analysis::{find_cycle_in, format_cycle};
+use common::random::{GenOptions, Rng};
+use common::{expect_split, parse_number, Answer, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+pub mod analysis;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input<'a> = (Rules, Box<[Update]>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (rules, updates) = input;
+        Ok(ordered_checksum(updates, rules).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (rules, updates) = input;
+        Ok(repaired_order_checksum(updates, rules)?.into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(200)))
+    }
+}
+
+pub fn repaired_order_checksum(updates: &[Update], rules: &Rules) -> Result<u32, String> {
+    updates
+        .iter()
+        .enumerate()
+        .filter(|(_, update)| !update.is_empty() && !is_update_sorted(update, rules))
+        .map(|(i, update)| {
+            let sorted = sort_update(update, rules).map_err(|unsortable| {
+                let pages: Vec<String> = update.iter().map(|page| page.to_string()).collect();
+                let pages = pages.join(",");
+                match unsortable {
+                    Unsortable::Cycle => format!(
+                        "update {} ({pages}) has no valid order, the rules for its pages form \
+                         the cycle {}",
+                        i + 1,
+                        format_cycle(&find_cycle_in(rules, update).unwrap_or_default())
+                    ),
+                    Unsortable::Unordered(left, right) => format!(
+                        "update {} ({pages}) has more than one valid order, no rule orders {left} \
+                         and {right}",
+                        i + 1
+                    ),
+                }
+            })?;
+            Ok(sorted[sorted.len() / 2])
+        })
+        .sum()
+}
+
+// why the rules between the pages of an update don't give them a single order
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Unsortable {
+    Cycle,
+    // neither has to come before the other, not even through other pages of the update
+    Unordered(u32, u32),
+}
+
+// Kahn's algorithm on the rules between the pages of the update: the next page is always the one
+// that no remaining page has to come before. If there are two of them, both orders are valid.
+fn sort_update(update: &[u32], rules: &Rules) -> Result<Box<[u32]>, Unsortable> {
+    let mut copies: HashMap<u32, usize> = HashMap::with_capacity(update.len());
+    for page in update {
+        *copies.entry(*page).or_default() += 1;
+    }
+    // how many remaining pages have to come before a page
+    let mut earlier: HashMap<u32, usize> = copies.keys().map(|page| (*page, 0)).collect();
+    for page in copies.keys() {
+        for later in rules.get(page).into_iter().flatten() {
+            if let Some(count) = earlier.get_mut(later) {
+                *count += 1;
+            }
+        }
+    }
+    let mut ready: Vec<u32> = earlier
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(page, _)| *page)
+        .collect();
+    ready.sort_unstable_by(|a, b| b.cmp(a));
+    let mut sorted: Vec<u32> = Vec::with_capacity(update.len());
+    while let Some(page) = ready.pop() {
+        if let Some(other) = ready.pop() {
+            return Err(Unsortable::Unordered(page.min(other), page.max(other)));
+        }
+        sorted.extend(std::iter::repeat_n(page, copies[&page]));
+        for later in rules.get(&page).into_iter().flatten() {
+            if let Some(count) = earlier.get_mut(later) {
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(*later);
+                }
+            }
+        }
+    }
+    // the pages on a cycle never become ready
+    if sorted.len() < update.len() {
+        return Err(Unsortable::Cycle);
+    }
+    Ok(sorted.into_boxed_slice())
+}
+
+pub fn ordered_checksum(updates: &[Update], rules: &Rules) -> u32 {
+    updates
+        .iter()
+        .filter(|update| !update.is_empty() && is_update_sorted(update, rules))
+        .map(|update| update[update.len() / 2])
+        .sum()
+}
+
+fn is_update_sorted(update: &[u32], rules: &Rules) -> bool {
+    let mut seen: HashSet<u32> = HashSet::with_capacity(update.len());
+    for page in update {
+        if let Some(later_pages) = rules.get(page) {
+            if later_pages
+                .iter()
+                .any(|later_page| seen.contains(later_page))
+            {
+                return false;
+            }
+        }
+        seen.insert(*page);
+    }
+    true
+}
+
+pub fn parse(input: &str) -> Result<(Rules, Box<[Update]>), ParseError> {
+    let (rules, updates) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::after(input, "an empty line between rules and updates"))?;
+    let mut rule_map: Rules = HashMap::with_capacity(128);
+    for rule in rules.lines().map(parse_rule) {
+        let (left, right) = rule?;
+        let entry = rule_map.entry(left).or_insert(Vec::with_capacity(8));
+        entry.push(right);
+    }
+    let updates: Box<[Update]> = updates
+        .lines()
+        .map(parse_update)
+        .collect::<Result<_, ParseError>>()?;
+    Ok((rule_map, updates))
+}
+
+pub type Rules = HashMap<u32, Vec<u32>>;
+
+type Rule = (u32, u32);
+fn parse_rule(line: &str) -> Result<Rule, ParseError> {
+    let (left, right) = expect_split(line, "|")?;
+    let left: u32 = parse_number(left)?;
+    let right: u32 = parse_number(right)?;
+
+    Ok((left, right))
+}
+
+pub type Update = Box<[u32]>;
+fn parse_update(line: &str) -> Result<Update, ParseError> {
+    line.split(',').map(parse_number).collect()
+}
+
+// Rules for every pair of 49 pages (like the real input), followed by `updates` updates with an odd
+// number of pages. About half of the updates are in the right order.
+pub fn generate(rng: &mut Rng, updates: usize) -> String {
+    let mut pages: Box<[u32]> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    let order = &pages[..49];
+    let mut rules: Vec<(u32, u32)> = Vec::with_capacity(order.len() * order.len() / 2);
+    for (i, left) in order.iter().enumerate() {
+        for right in &order[i + 1..] {
+            rules.push((*left, *right));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let mut input = String::with_capacity(rules.len() * 6 + updates * 40);
+    for (left, right) in rules {
+        let _ = writeln!(input, "{left}|{right}");
+    }
+    input.push('\n');
+    let mut positions: Box<[usize]> = (0..order.len()).collect();
+    for _ in 0..updates {
+        rng.shuffle(&mut positions);
+        let update = &mut positions[..rng.range_usize(2..12) * 2 + 1];
+        if rng.chance(1, 2) {
+            update.sort_unstable();
+        }
+        let update: Box<[String]> = update.iter().map(|p| order[*p].to_string()).collect();
+        input.push_str(&update.join(","));
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::random::{check_property, Rng};
+
+    static EXAMPLE: &str = r#"47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+"#;
+
+    #[test]
+    fn ordered_checksum_works_for_example() {
+        // given
+        let (rules, updates) = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let sum = ordered_checksum(&updates, &rules);
+
+        // then
+        assert_eq!(sum, 143);
+    }
+
+    #[test]
+    fn repaired_order_checksum_works_for_example() {
+        // given
+        let (rules, updates) = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let sum = repaired_order_checksum(&updates, &rules);
+
+        // then
+        assert_eq!(sum, Ok(123));
+    }
+
+    #[test]
+    fn repaired_order_checksum_names_the_update_without_order() {
+        // given
+        let (rules, updates) =
+            parse("1|2\n2|3\n3|1\n\n1,2\n2,1,3\n").expect("expected input to parse");
+
+        // when
+        let sum = repaired_order_checksum(&updates, &rules);
+
+        // then
+        assert_eq!(
+            sum,
+            Err(
+                "update 2 (2,1,3) has no valid order, the rules for its pages form the cycle \
+                 1 -> 2 -> 3 -> 1"
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn repaired_order_checksum_follows_rules_through_other_pages() {
+        // given
+        let (rules, updates) = parse("1|2\n2|3\n\n3,1,2\n").expect("expected input to parse");
+
+        // when
+        let sum = repaired_order_checksum(&updates, &rules);
+
+        // then
+        assert_eq!(sum, Ok(2));
+    }
+
+    #[test]
+    fn repaired_order_checksum_names_the_update_with_many_orders() {
+        // given
+        let (rules, updates) = parse("1|2\n1|3\n\n2,3,1\n").expect("expected input to parse");
+
+        // when
+        let sum = repaired_order_checksum(&updates, &rules);
+
+        // then
+        assert_eq!(
+            sum,
+            Err(
+                "update 1 (2,3,1) has more than one valid order, no rule orders 2 and 3".to_owned()
+            )
+        );
+    }
+
+    // Like the real inputs, the rules order every pair of pages that appear in an update
+    // together, consistent with some hidden order of all pages.
+    fn random_rules_and_update(rng: &mut Rng) -> (Rules, Update) {
+        let mut pages: Vec<u32> = (10..100).collect();
+        rng.shuffle(&mut pages);
+        pages.truncate(rng.range_usize(1..25));
+        let mut rules: Rules = HashMap::with_capacity(pages.len());
+        for (i, left) in pages.iter().enumerate() {
+            rules.insert(*left, pages[i + 1..].to_vec());
+        }
+        let mut update = pages.clone();
+        rng.shuffle(&mut update);
+        update.truncate(rng.range_usize(1..pages.len() + 1));
+        (rules, update.into_boxed_slice())
+    }
+
+    #[test]
+    fn sort_update_sorts_by_rules() {
+        check_property(500, |rng| {
+            // given
+            let (rules, update) = random_rules_and_update(rng);
+
+            // when
+            let sorted = sort_update(&update, &rules)
+                .map_err(|e| format!("{update:?} is unsortable: {e:?}"))?;
+
+            // then
+            if !is_update_sorted(&sorted, &rules) {
+                return Err(format!("{update:?} sorted to {sorted:?}"));
+            }
+            let mut expected = update.to_vec();
+            let mut actual = sorted.to_vec();
+            expected.sort_unstable();
+            actual.sort_unstable();
+            if expected != actual {
+                return Err(format!("pages of {update:?} changed to {sorted:?}"));
+            }
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,226 @@
+use common::random::{GenOptions, Rng};
+use common::{par_map, Animation, Answer, Cell, Color, Frame, ParseError, Solution};
+use grid::{Grid, Offset, Pos};
+use std::collections::HashSet;
+use std::iter::successors;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input<'a> = Map;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(track_guard(input)?.len().into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(obstruction_candidates(input)?.into())
+    }
+
+    fn animation<'a>(input: &'a Self::Input<'_>) -> Option<Animation<'a>> {
+        Some(Box::new(walk_frames(input)))
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(130)))
+    }
+}
+
+pub fn obstruction_candidates(map: &Map) -> Result<usize, String> {
+    // Hello brute force my old friend
+    let visited: Vec<Pos> = track_guard(map)?.into_iter().collect();
+    let candidates = par_map(&visited, |obstruction_pos| {
+        let mut rocks = map.rocks.clone();
+        rocks[*obstruction_pos] = true;
+        let updated_map = Map { rocks, ..*map };
+        has_loop(&updated_map)
+    });
+    Ok(candidates.into_iter().filter(|is_loop| *is_loop).count())
+}
+
+// Position and direction of the guard after every step, until the guard leaves the map. Turning
+// counts as a step. In a loop it never ends.
+fn guard_walk(map: &Map) -> impl Iterator<Item = (Pos, Offset)> + '_ {
+    successors(Some((map.guard, (0, -1))), |&(pos, dir)| {
+        match map.rocks.offset(pos, dir) {
+            None => None,
+            Some(next) if map.rocks[next] => Some((pos, (-dir.1, dir.0))),
+            Some(next) => Some((next, dir)),
+        }
+    })
+}
+
+fn has_loop(map: &Map) -> bool {
+    let mut visited: HashSet<(Pos, Offset)> = HashSet::with_capacity(map.rocks.len());
+    guard_walk(map).any(|state| !visited.insert(state))
+}
+
+pub fn track_guard(map: &Map) -> Result<HashSet<Pos>, String> {
+    let mut states: HashSet<(Pos, Offset)> = HashSet::with_capacity(map.rocks.len());
+    let mut visited: HashSet<Pos> = HashSet::with_capacity(map.rocks.len());
+    for (pos, dir) in guard_walk(map) {
+        if !states.insert((pos, dir)) {
+            return Err("the guard walks in a loop and never leaves the map".to_owned());
+        }
+        visited.insert(pos);
+    }
+    Ok(visited)
+}
+
+// One frame per step of the guard, with the positions visited so far highlighted. Stops when the
+// guard leaves the map or starts walking in a loop.
+pub fn walk_frames(map: &Map) -> impl Iterator<Item = Frame> + '_ {
+    let mut states: HashSet<(Pos, Offset)> = HashSet::with_capacity(map.rocks.len());
+    let mut visited: HashSet<Pos> = HashSet::with_capacity(map.rocks.len());
+    guard_walk(map).map_while(move |(pos, dir)| {
+        if !states.insert((pos, dir)) {
+            return None;
+        }
+        visited.insert(pos);
+        let mut picture = map.rocks.picture(|rock| match rock {
+            true => Cell::new('#').fg(Color::GRAY),
+            false => Cell::new('.').fg(Color::DARK_GRAY),
+        });
+        picture.highlight(visited.iter().copied(), Color::BLUE);
+        let guard = match dir {
+            (0, -1) => '^',
+            (1, 0) => '>',
+            (0, 1) => 'v',
+            _ => '<',
+        };
+        picture.set(pos, Cell::new(guard).fg(Color::YELLOW).bg(Color::RED));
+        Some(Frame {
+            picture,
+            caption: format!("{} positions visited", visited.len()),
+        })
+    })
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Map {
+    rocks: Grid<bool>,
+    guard: Pos,
+}
+
+pub fn parse(map: &str) -> Result<Map, ParseError> {
+    let mut guard: Option<Pos> = None;
+    let rocks = Grid::parse(map, "'.', '#' or '^'", |c, pos| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        '^' => {
+            guard.get_or_insert(pos);
+            Some(false)
+        }
+        _ => None,
+    })?;
+    let guard =
+        guard.ok_or_else(|| ParseError::after(map, "a guard ('^') somewhere in the map"))?;
+
+    Ok(Map { rocks, guard })
+}
+
+// how many maps `generate` tries, random maps often let the guard leave after a few steps
+const GENERATED_MAPS: usize = 20;
+
+// A square map with about one rock in 20 tiles and the guard somewhere on a free tile. Of the
+// maps where the guard leaves (the puzzle expects that), the one with the longest walk is taken.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut longest: Option<(usize, Map)> = None;
+    let mut tries = 0;
+    while tries < GENERATED_MAPS || longest.is_none() {
+        tries += 1;
+        let rocks = Grid::from_fn(size, size, |_| rng.chance(1, 20));
+        let guard = (rng.range_usize(0..size), rng.range_usize(0..size));
+        let mut map = Map { rocks, guard };
+        if let Some(rock) = map.rocks.get_mut(guard) {
+            *rock = false;
+        }
+        if let Ok(visited) = track_guard(&map) {
+            if longest.as_ref().is_none_or(|(len, _)| visited.len() > *len) {
+                longest = Some((visited.len(), map));
+            }
+        }
+    }
+
+    let Some((_, map)) = longest else {
+        unreachable!("the loop only ends with a map");
+    };
+    let mut input = map.rocks.map(|rock| if *rock { '#' } else { '.' });
+    input[map.guard] = '^';
+    input.render(|c| *c)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE: &str = r#"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+"#;
+
+    #[test]
+    fn track_guard_works_for_example() {
+        // given
+        let map = parse(EXAMPLE).expect("expected exampe input to parse");
+
+        // when
+        let positions = track_guard(&map);
+
+        // then
+        assert_eq!(positions.map(|p| p.len()), Ok(41));
+    }
+
+    #[test]
+    fn track_guard_reports_a_loop() {
+        // given
+        let map = parse(".#..\n...#\n#^..\n..#.\n").expect("expected input to parse");
+
+        // when
+        let positions = track_guard(&map);
+
+        // then
+        assert!(positions.is_err());
+    }
+
+    #[test]
+    fn obstruction_candidates_works_for_example() {
+        // given
+        let map = parse(EXAMPLE).expect("expected exampe input to parse");
+
+        // when
+        let obstructions = obstruction_candidates(&map);
+
+        // then
+        assert_eq!(obstructions, Ok(6));
+    }
+
+    #[test]
+    fn walk_frames_end_when_the_guard_leaves() {
+        // given
+        let map = parse(EXAMPLE).expect("expected exampe input to parse");
+
+        // when
+        let last = walk_frames(&map).last();
+
+        // then
+        assert_eq!(
+            last.map(|frame| frame.caption),
+            Some("41 positions visited".to_owned())
+        );
+    }
+}
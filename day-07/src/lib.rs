@@ -0,0 +1,267 @@
+use common::random::{GenOptions, Rng};
+use common::{expect_split, par_map, parse_number, Answer, ParseError, Solution};
+use std::fmt::Write;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input<'a> = Box<[Equation]>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(total_calibration_result(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(concat_calibration_result(input).into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(850)))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Op {
+    Add,
+    Mul,
+    Cat,
+}
+
+pub fn concat_calibration_result(equations: &[Equation]) -> u64 {
+    par_map(equations, |(lhs, rhs)| {
+        if possibly_valid_concat(*lhs, rhs) {
+            *lhs
+        } else {
+            0
+        }
+    })
+    .into_iter()
+    .sum()
+}
+
+fn possibly_valid_concat(lhs: u64, rhs: &[u64]) -> bool {
+    if rhs.is_empty() {
+        return false;
+    }
+    let mut operators: Box<[Op]> = (0..(rhs.len() - 1)).map(|_| Op::Add).collect();
+    let mut has_next = true;
+    while has_next {
+        let mut result = rhs[0];
+        for (operand, operator) in rhs[1..].iter().zip(operators.iter()) {
+            result = match operator {
+                Op::Add => result + operand,
+                Op::Mul => result * operand,
+                Op::Cat => concat(result, *operand),
+            };
+        }
+        if result == lhs {
+            return true;
+        }
+        has_next = next_op(&mut operators);
+    }
+    false
+}
+
+fn next_op(operators: &mut [Op]) -> bool {
+    let mut carry = true;
+    let mut i = 0;
+    while carry && i < operators.len() {
+        (operators[i], carry) = match operators[i] {
+            Op::Add => (Op::Mul, false),
+            Op::Mul => (Op::Cat, false),
+            Op::Cat => (Op::Add, true),
+        };
+        i += 1;
+    }
+    !carry
+}
+
+fn concat(lhs: u64, rhs: u64) -> u64 {
+    lhs * 10u64.pow(rhs.checked_ilog10().unwrap_or(0) + 1) + rhs
+}
+
+pub fn total_calibration_result(equations: &[Equation]) -> u64 {
+    par_map(
+        equations,
+        |(lhs, rhs)| {
+            if possibly_valid(*lhs, rhs) {
+                *lhs
+            } else {
+                0
+            }
+        },
+    )
+    .into_iter()
+    .sum()
+}
+
+fn possibly_valid(lhs: u64, rhs: &[u64]) -> bool {
+    if rhs.is_empty() {
+        return false;
+    }
+    (0u64..1 << (rhs.len() - 1)).any(|operators| calculate(rhs, operators) == lhs)
+}
+
+fn calculate(numbers: &[u64], operators: u64) -> u64 {
+    if numbers.is_empty() {
+        return 0;
+    }
+    let mut result = numbers[0];
+    for (i, number) in numbers[1..].iter().enumerate() {
+        if (operators >> i) & 1 == 0 {
+            result += number;
+        } else {
+            result *= number;
+        }
+    }
+    result
+}
+
+pub fn parse(input: &str) -> Result<Box<[Equation]>, ParseError> {
+    input.lines().map(parse_equation).collect()
+}
+
+pub type Equation = (u64, Box<[u64]>);
+fn parse_equation(line: &str) -> Result<Equation, ParseError> {
+    let (left, right) = expect_split(line, ": ")?;
+    let left: u64 = parse_number(left)?;
+    let right: Box<[u64]> = right
+        .split(' ')
+        .map(parse_number)
+        .collect::<Result<_, _>>()?;
+
+    Ok((left, right))
+}
+
+// Equations with 2 to 12 numbers of up to three digits. The test value is calculated with random
+// operators, about a third of them is off by one afterwards to make the equation impossible (or
+// at least unlikely to be possible). All numbers together have at most 18 digits, so even
+// concatenating all of them fits into a `u64`.
+pub fn generate(rng: &mut Rng, lines: usize) -> String {
+    let mut input = String::with_capacity(lines * 32);
+    for _ in 0..lines {
+        let count = rng.range_usize(2..13);
+        let mut numbers: Vec<u64> = Vec::with_capacity(count);
+        let mut digits = 0;
+        while numbers.len() < count {
+            let number = 10u64.pow(rng.range_usize(0..3) as u32) * rng.range(1..10) as u64
+                + rng.range(0..10) as u64;
+            let len = number.ilog10() + 1;
+            if digits + len > 18 {
+                break;
+            }
+            digits += len;
+            numbers.push(number);
+        }
+        let mut result = numbers[0];
+        for number in &numbers[1..] {
+            result = match rng.below(3) {
+                0 => result + number,
+                1 => result * number,
+                _ => concat(result, *number),
+            };
+        }
+        if rng.chance(1, 3) {
+            result += 1;
+        }
+        let numbers: Box<[String]> = numbers.iter().map(u64::to_string).collect();
+        let _ = writeln!(input, "{result}: {}", numbers.join(" "));
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE: &str = r#"190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+"#;
+
+    #[test]
+    fn total_calibration_result_works_for_example() {
+        // given
+        let equations = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let tcr = total_calibration_result(&equations);
+
+        // then
+        assert_eq!(tcr, 3749);
+    }
+
+    #[test]
+    fn concat_calibration_result_works_for_example() {
+        // given
+        let equations = parse(EXAMPLE).expect("expcted exampe input to parse");
+
+        // when
+        let tcr = concat_calibration_result(&equations);
+
+        // then
+        assert_eq!(tcr, 11387);
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(concat(12, 345), 12345);
+        assert_eq!(concat(0, 345), 345);
+        assert_eq!(concat(12, 0), 120);
+    }
+
+    #[test]
+    fn next_op_works_for_round_trip() {
+        // given
+        let mut ops = [Op::Add, Op::Add];
+
+        // when/then
+        let fine = next_op(&mut ops);
+        assert!(fine); // This is fine.
+        assert_eq!(&ops, &[Op::Mul, Op::Add]);
+
+        let fine = next_op(&mut ops);
+        assert!(fine); // This is fine.
+        assert_eq!(&ops, &[Op::Cat, Op::Add]);
+
+        let fine = next_op(&mut ops);
+        assert!(fine); // This is fine.
+        assert_eq!(&ops, &[Op::Add, Op::Mul]);
+
+        let fine = next_op(&mut ops);
+        assert!(fine); // This is fine.
+        assert_eq!(&ops, &[Op::Mul, Op::Mul]);
+
+        let fine = next_op(&mut ops);
+        assert!(fine); // This is fine.
+        assert_eq!(&ops, &[Op::Cat, Op::Mul]);
+
+        let fine = next_op(&mut ops);
+        assert!(fine); // This is fine.
+        assert_eq!(&ops, &[Op::Add, Op::Cat]);
+
+        let fine = next_op(&mut ops);
+        assert!(fine); // This is fine.
+        assert_eq!(&ops, &[Op::Mul, Op::Cat]);
+
+        let fine = next_op(&mut ops);
+        assert!(fine); // This is fine.
+        assert_eq!(&ops, &[Op::Cat, Op::Cat]);
+
+        let fine = next_op(&mut ops);
+        assert!(!fine);
+        assert_eq!(&ops, &[Op::Add, Op::Add]);
+    }
+}
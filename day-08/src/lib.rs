@@ -0,0 +1,183 @@
+use common::random::{GenOptions, Rng};
+use common::{Answer, Cell, Color, ParseError, Picture, Solution};
+use grid::{Grid, Offset, Pos};
+use std::collections::{HashMap, HashSet};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input<'a> = Map;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(find_antinodes(input).len().into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(find_resonant_antinodes(input).len().into())
+    }
+
+    fn picture(input: &Self::Input<'_>) -> Result<Option<Picture>, String> {
+        Ok(Some(antinode_picture(input)))
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(50)))
+    }
+}
+
+// The antennas with the antinodes of part 1 in red and the additional ones of part 2 in orange.
+pub fn antinode_picture(map: &Map) -> Picture {
+    let mut picture = map.area.picture(|c| match c {
+        '.' => Cell::new('.').fg(Color::DARK_GRAY),
+        c => Cell::new(*c).fg(Color::WHITE),
+    });
+    picture.highlight(find_resonant_antinodes(map), Color::ORANGE);
+    picture.highlight(find_antinodes(map), Color::RED);
+    picture
+}
+
+pub fn find_resonant_antinodes(map: &Map) -> HashSet<Pos> {
+    let mut antinodes: HashSet<Pos> = HashSet::with_capacity(map.antennas.len() * 16);
+    for antennas in map.antennas.values() {
+        for (i, (x1, y1)) in antennas.iter().enumerate() {
+            for (x2, y2) in &antennas[i + 1..] {
+                let dx = x2 - x1;
+                let dy = y2 - y1;
+                let mut ax = *x2;
+                let mut ay = *y2;
+                while let Some(pos) = map.area.checked_pos((ax, ay)) {
+                    antinodes.insert(pos);
+                    ax += dx;
+                    ay += dy;
+                }
+                ax = *x1;
+                ay = *y1;
+                while let Some(pos) = map.area.checked_pos((ax, ay)) {
+                    antinodes.insert(pos);
+                    ax -= dx;
+                    ay -= dy;
+                }
+            }
+        }
+    }
+    antinodes
+}
+
+pub fn find_antinodes(map: &Map) -> HashSet<Pos> {
+    let mut antinodes: HashSet<Pos> = HashSet::with_capacity(map.antennas.len() * 16);
+    for antennas in map.antennas.values() {
+        for (i, (x1, y1)) in antennas.iter().enumerate() {
+            for (x2, y2) in &antennas[i + 1..] {
+                let dx = x2 - x1;
+                let dy = y2 - y1;
+                antinodes.extend(map.area.checked_pos((x2 + dx, y2 + dy)));
+                antinodes.extend(map.area.checked_pos((x1 - dx, y1 - dy)));
+            }
+        }
+    }
+    antinodes
+}
+
+#[derive(Clone, Debug)]
+pub struct Map {
+    area: Grid<char>,
+    antennas: HashMap<char, Vec<Offset>>,
+}
+
+pub fn parse(content: &str) -> Result<Map, ParseError> {
+    let area = Grid::parse(content, "a character", |c, _| Some(c))?;
+
+    let mut antennas: HashMap<char, Vec<Offset>> = HashMap::with_capacity(area.height() * 5);
+    for ((x, y), a) in area.iter().filter(|(_, a)| **a != '.') {
+        antennas
+            .entry(*a)
+            .or_insert(Vec::with_capacity(16))
+            .push((x as isize, y as isize));
+    }
+
+    Ok(Map { area, antennas })
+}
+
+const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// A square map with about one antenna in 12 tiles. There are 4 antennas per frequency on average,
+// on real maps they come in small groups as well.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let antennas = size * size / 12;
+    let frequencies = &FREQUENCIES[..(antennas / 4).clamp(1, FREQUENCIES.len())];
+    let mut map = Grid::filled(size, size, '.');
+    for _ in 0..antennas {
+        let pos = (rng.range_usize(0..size), rng.range_usize(0..size));
+        map[pos] = *rng.choose(frequencies) as char;
+    }
+    map.render(|c| *c)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE: &str = r#"............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+"#;
+
+    #[test]
+    fn find_antinodes_works_for_example() {
+        // given
+        let map = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let antinodes = find_antinodes(&map);
+
+        // then
+        assert_eq!(antinodes.len(), 14);
+    }
+
+    #[test]
+    fn find_resonant_antinodes_works_for_example() {
+        // given
+        let map = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let antinodes = find_resonant_antinodes(&map);
+
+        // then
+        assert_eq!(antinodes.len(), 34);
+    }
+
+    #[test]
+    fn antinode_picture_highlights_both_kinds_of_antinodes() {
+        // given
+        let map = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let picture = antinode_picture(&map);
+
+        // then
+        let count = |color: Color| {
+            picture
+                .rows()
+                .flatten()
+                .filter(|cell| cell.bg == Some(color))
+                .count()
+        };
+        assert_eq!(count(Color::RED), 14);
+        assert_eq!(count(Color::ORANGE), 34 - 14);
+    }
+}
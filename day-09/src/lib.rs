@@ -0,0 +1,289 @@
+use common::random::{GenOptions, Rng};
+use common::{Answer, ParseError, Solution};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input<'a> = Box<[u8]>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(fragment_disk(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(defragment_disk(input).into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(19999)))
+    }
+}
+
+pub fn defragment_disk(original_disk: &[u8]) -> u64 {
+    disk_checksum(&defragment(original_disk))
+}
+
+// moves whole files to the leftmost free space that fits them, highest id first
+fn defragment(original_disk: &[u8]) -> Box<[Option<u16>]> {
+    /*let mut compressed_disk: Box<[(u8, Option<u16>)]> = original_disk
+    .iter()
+    .enumerate()
+    .map(|(i, size)| {
+        (
+            *size,
+            if i % 2 == 0 {
+                Some((i / 2) as u16)
+            } else {
+                None
+            },
+        )
+    })
+    .collect();*/
+    let mut disk = decompress_disk(original_disk);
+    let mut u: usize = disk.len().saturating_sub(1);
+    let mut max_id: u16 = (original_disk.len() / 2) as u16;
+    while u > 0 {
+        while u > 0 && disk[u].map(|id| id > max_id).unwrap_or(true) {
+            u -= 1;
+        }
+        // nothing before the first block to move a file to
+        if u == 0 {
+            break;
+        }
+        let mut ul = u;
+        while ul > 0 && disk[ul] == disk[u] {
+            ul -= 1;
+        }
+        let size = u - ul;
+        if let Some(target_offset) = find_space(&disk[0..=ul], size) {
+            for i in 0..size {
+                disk[target_offset + i] = disk[ul + 1 + i];
+                disk[ul + 1 + i] = None;
+            }
+        }
+        max_id = max_id.max(1) - 1;
+        u = ul;
+    }
+    disk
+}
+
+// return offset of first space available that is large enough to fit len, or None if there is no
+// such space
+fn find_space(disk: &[Option<u16>], len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let mut offset = 0;
+    while offset + len <= disk.len() {
+        if disk[offset].is_some() {
+            offset += 1;
+        } else {
+            if (offset..(offset + len)).all(|i| disk[i].is_none()) {
+                return Some(offset);
+            }
+            offset += 1;
+        }
+    }
+    None
+}
+
+pub fn fragment_disk(original_disk: &[u8]) -> u64 {
+    disk_checksum(&fragment(original_disk))
+}
+
+// moves single blocks from the end into the leftmost free space
+fn fragment(original_disk: &[u8]) -> Box<[Option<u16>]> {
+    let mut disk = decompress_disk(original_disk);
+    let mut l: usize = 0;
+    let mut u: usize = disk.len().saturating_sub(1);
+
+    while l < u {
+        if disk[u].is_some() {
+            if disk[l].is_none() {
+                disk[l] = disk[u];
+                disk[u] = None;
+                u -= 1;
+                l += 1;
+            } else {
+                l += 1;
+            }
+        } else {
+            u -= 1;
+        }
+    }
+    disk
+}
+
+fn disk_checksum(disk: &[Option<u16>]) -> u64 {
+    disk.iter()
+        .enumerate()
+        .filter_map(|(i, id)| {
+            let id = (*id)?;
+            Some((i, id))
+        })
+        .map(|(i, id)| i as u64 * id as u64)
+        .sum()
+}
+
+fn decompress_disk(compressed: &[u8]) -> Box<[Option<u16>]> {
+    let mut decompressed: Vec<Option<u16>> = Vec::with_capacity(10 * compressed.len());
+
+    // files and free space alternate, `parse` makes sure the ids fit
+    for (i, section_length) in compressed.iter().enumerate() {
+        let block = if i % 2 == 0 {
+            Some((i / 2) as u16)
+        } else {
+            None
+        };
+        for _ in 0..*section_length {
+            decompressed.push(block);
+        }
+    }
+
+    decompressed.into_boxed_slice()
+}
+
+// every other digit is a file, so this many digits give one file per u16 id
+const MAX_DIGITS: usize = 2 * (u16::MAX as usize + 1) - 1;
+
+pub fn parse(input: &str) -> Result<Box<[u8]>, ParseError> {
+    let disk_map = input.trim();
+    if disk_map.is_empty() {
+        return Err(ParseError::after(input, "at least one digit"));
+    }
+    let digits: Box<[u8]> = disk_map
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .map(|d| d as u8)
+                .ok_or_else(|| ParseError::at_char(disk_map, i, "a digit"))
+        })
+        .collect::<Result<_, _>>()?;
+    if digits.len() > MAX_DIGITS {
+        // all characters are digits, so this is a char boundary
+        return Err(ParseError::at_char(
+            disk_map,
+            MAX_DIGITS,
+            format!("at most {MAX_DIGITS} digits"),
+        ));
+    }
+    Ok(digits)
+}
+
+// A disk map of `len` digits (at most `MAX_DIGITS`), files have 1 to 9 blocks, free space 0 to 9.
+pub fn generate(rng: &mut Rng, len: usize) -> String {
+    let len = len.clamp(1, MAX_DIGITS);
+    let mut disk_map = String::with_capacity(len + 1);
+    for i in 0..len {
+        let digit = if i % 2 == 0 {
+            rng.range(1..10)
+        } else {
+            rng.range(0..10)
+        };
+        disk_map.push(char::from(b'0' + digit as u8));
+    }
+    disk_map.push('\n');
+    disk_map
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::random::{check_property, Rng};
+
+    static EXAMPLE: &str = "2333133121414131402\n";
+
+    #[test]
+    fn fragment_disk_works_for_example() {
+        // given
+        let original_disk = parse(EXAMPLE).expect("expected example input to parse");
+
+        // then
+        let checksum = fragment_disk(&original_disk);
+
+        // then
+        assert_eq!(checksum, 1928);
+    }
+
+    #[test]
+    fn defragment_disk_works_for_example() {
+        // given
+        let original_disk = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let checksum = defragment_disk(&original_disk);
+
+        // then
+        assert_eq!(checksum, 2858);
+    }
+
+    // like the real inputs: files have 1 to 9 blocks, the free space between them 0 to 9
+    fn random_disk_map(rng: &mut Rng) -> Box<[u8]> {
+        let len = rng.range_usize(1..60);
+        (0..len)
+            .map(|i| {
+                let min = if i % 2 == 0 { 1 } else { 0 };
+                rng.range(min..10) as u8
+            })
+            .collect()
+    }
+
+    fn file_blocks(disk: &[Option<u16>]) -> Vec<u16> {
+        let mut blocks: Vec<u16> = disk.iter().flatten().copied().collect();
+        blocks.sort_unstable();
+        blocks
+    }
+
+    #[test]
+    fn fragment_keeps_file_blocks_and_leaves_no_gaps() {
+        check_property(500, |rng| {
+            // given
+            let disk_map = random_disk_map(rng);
+
+            // when
+            let disk = fragment(&disk_map);
+
+            // then
+            if file_blocks(&disk) != file_blocks(&decompress_disk(&disk_map)) {
+                return Err(format!("blocks of {disk_map:?} changed"));
+            }
+            let files = disk.iter().filter(|block| block.is_some()).count();
+            if disk[..files].iter().any(|block| block.is_none()) {
+                return Err(format!("gap left in {disk_map:?}"));
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn defragment_keeps_file_blocks_and_files_whole() {
+        check_property(500, |rng| {
+            // given
+            let disk_map = random_disk_map(rng);
+
+            // when
+            let disk = defragment(&disk_map);
+
+            // then
+            if file_blocks(&disk) != file_blocks(&decompress_disk(&disk_map)) {
+                return Err(format!("blocks of {disk_map:?} changed"));
+            }
+            for id in 0..disk_map.len().div_ceil(2) as u16 {
+                let first = disk.iter().position(|block| *block == Some(id));
+                let last = disk.iter().rposition(|block| *block == Some(id));
+                if let (Some(first), Some(last)) = (first, last) {
+                    if disk[first..=last].iter().any(|block| *block != Some(id)) {
+                        return Err(format!("file {id} of {disk_map:?} was split"));
+                    }
+                }
+            }
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,143 @@
+use common::random::{GenOptions, Rng};
+use common::{Answer, ParseError, Solution};
+use grid::{Grid, Pos};
+use search::{bfs, Search};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input<'a> = Map;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(find_all_valid_trails(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(rate_all_trailheads(input).into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(57)))
+    }
+}
+
+pub fn find_all_valid_trails(map: &Map) -> usize {
+    map.iter()
+        .filter(|(_, height)| **height == 0)
+        .map(|(pos, _)| find_valid_trails_from(map, pos))
+        .sum()
+}
+
+fn hike(map: &Map, start: Pos) -> Search<Pos> {
+    bfs(
+        [start],
+        |&pos: &Pos| {
+            map.neighbours4(pos)
+                .filter(move |next| map[*next] == map[pos] + 1)
+        },
+        |_| false,
+    )
+}
+
+fn find_valid_trails_from(map: &Map, start: Pos) -> usize {
+    hike(map, start)
+        .distances()
+        .keys()
+        .filter(|pos| map[**pos] == 9)
+        .count()
+}
+
+pub fn rate_all_trailheads(map: &Map) -> usize {
+    map.iter()
+        .filter(|(_, height)| **height == 0)
+        .map(|(pos, _)| rate_trailhead(map, pos))
+        .sum()
+}
+
+fn rate_trailhead(map: &Map, start: Pos) -> usize {
+    // every step goes up by one, so all trails to a top have the same length and are optimal
+    let search = hike(map, start);
+    search
+        .distances()
+        .keys()
+        .filter(|pos| map[**pos] == 9)
+        .map(|top| search.count_paths_to(top) as usize)
+        .sum()
+}
+
+pub type Map = Grid<u8>;
+
+pub fn parse(input: &str) -> Result<Map, ParseError> {
+    Grid::parse(input, "a height digit", |c, _| {
+        c.to_digit(10).map(|d| d as u8)
+    })
+}
+
+// A square map of random heights with hiking trails planted into it. Every trail is a random walk
+// from a 0 up to a 9, later trails may cross earlier ones.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut map: Map = Grid::from_fn(size, size, |_| rng.range(0..10) as u8);
+    for _ in 0..size * size / 30 + 1 {
+        let mut pos = (rng.range_usize(0..size), rng.range_usize(0..size));
+        let mut trail: Vec<Pos> = Vec::with_capacity(10);
+        for height in 0..10 {
+            map[pos] = height;
+            trail.push(pos);
+            let next: Vec<Pos> = map
+                .neighbours4(pos)
+                .filter(|next| !trail.contains(next))
+                .collect();
+            if next.is_empty() {
+                break;
+            }
+            pos = *rng.choose(&next);
+        }
+    }
+    map.render(|height| char::from(b'0' + height))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE: &str = r#"89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+"#;
+
+    #[test]
+    fn find_all_valid_trails_works_for_example() {
+        // given
+        let map = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let scores = find_all_valid_trails(&map);
+
+        // then
+        assert_eq!(scores, 36);
+    }
+
+    #[test]
+    fn rate_all_trailheads_works_for_example() {
+        // given
+        let map = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let ratings = rate_all_trailheads(&map);
+
+        // then
+        assert_eq!(ratings, 81);
+    }
+}
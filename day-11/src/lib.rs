@@ -0,0 +1,97 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{parse_number, Answer, ParseError, Solution};
+use std::collections::HashMap;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input<'a> = Box<[u128]>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(dynamic_blinks(input, 25).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(dynamic_blinks(input, 75).into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(8)))
+    }
+}
+
+pub fn dynamic_blinks(stones: &[u128], n: u32) -> u64 {
+    let mut cache: HashMap<(u128, u32), u64> = HashMap::with_capacity(1024);
+    let mut stone_count = 0;
+
+    for stone in stones {
+        stone_count += dynamic_blinks_internal(*stone, n, &mut cache);
+    }
+    stone_count
+}
+
+fn dynamic_blinks_internal(stone: u128, n: u32, cache: &mut HashMap<(u128, u32), u64>) -> u64 {
+    if n == 0 {
+        return 1;
+    }
+    if let Some(stones) = cache.get(&(stone, n)) {
+        return *stones;
+    }
+    let stones = if stone == 0 {
+        dynamic_blinks_internal(1, n - 1, cache)
+    } else if stone.ilog10() % 2 == 1 {
+        let digits = stone.ilog10() + 1;
+        let div = 10u128.pow(digits / 2);
+        dynamic_blinks_internal(stone / div, n - 1, cache)
+            + dynamic_blinks_internal(stone % div, n - 1, cache)
+    } else {
+        dynamic_blinks_internal(stone * 2024, n - 1, cache)
+    };
+    cache.insert((stone, n), stones);
+    stones
+}
+
+pub fn parse(input: &str) -> Result<Box<[u128]>, ParseError> {
+    input.split_whitespace().map(parse_number).collect()
+}
+
+// Stones with numbers of 1 to 7 digits, a few of them 0.
+pub fn generate(rng: &mut Rng, stones: usize) -> String {
+    let stones: Box<[String]> = (0..stones)
+        .map(|_| {
+            if rng.chance(1, 10) {
+                0
+            } else {
+                let digits = rng.range(1..8) as u32;
+                rng.range(1..10i64.pow(digits))
+            }
+        })
+        .map(|stone| stone.to_string())
+        .collect();
+    stones.join(" ") + "\n"
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dynamic_blinks_works_for_example() {
+        // given
+        let stones = parse("125 17\n").expect("expect example input to parse");
+
+        // when
+        let stones = dynamic_blinks(&stones, 25);
+
+        // then
+        assert_eq!(stones, 55312);
+    }
+}
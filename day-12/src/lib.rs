@@ -0,0 +1,469 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{Answer, Cell, Color, ParseError, Picture, Solution};
+use grid::{Grid, Pos};
+use std::collections::HashSet;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input<'a> = Garden;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(fence_price(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(fence_discount_price(input).into())
+    }
+
+    fn picture(input: &Self::Input<'_>) -> Result<Option<Picture>, String> {
+        Ok(Some(fence_picture(input)))
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(140)))
+    }
+}
+
+const REGION_COLORS: [Color; 6] = [
+    Color::BLUE,
+    Color::GREEN,
+    Color::YELLOW,
+    Color::ORANGE,
+    Color::CYAN,
+    Color::MAGENTA,
+];
+
+// The regions in different colours with the fences around them. Plots are at the odd positions
+// of the picture, the fences in between them.
+pub fn fence_picture(garden: &Garden) -> Picture {
+    let regions = regions(garden);
+    let color = |px: usize, py: usize| {
+        let plot = (px.saturating_sub(1) / 2, py.saturating_sub(1) / 2);
+        REGION_COLORS[regions[plot] % REGION_COLORS.len()]
+    };
+    // a fence is needed between plots with different crops and at the border
+    let fence = |px: isize, py: isize| match (px.rem_euclid(2), py.rem_euclid(2)) {
+        (0, 1) => garden.get_signed((px / 2 - 1, py / 2)) != garden.get_signed((px / 2, py / 2)),
+        (1, 0) => garden.get_signed((px / 2, py / 2 - 1)) != garden.get_signed((px / 2, py / 2)),
+        _ => false,
+    };
+    let width = garden.width() * 2 + 1;
+    let height = garden.height() * 2 + 1;
+    Picture::from_fn(width, height, |(px, py)| {
+        let (x, y) = (px as isize, py as isize);
+        match (px % 2, py % 2) {
+            (1, 1) => Cell::new(garden[(px / 2, py / 2)])
+                .fg(Color::BLACK)
+                .bg(color(px, py)),
+            (0, 1) if fence(x, y) => Cell::new('|').fg(Color::WHITE),
+            (1, 0) if fence(x, y) => Cell::new('-').fg(Color::WHITE),
+            (0, 0)
+                if [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                    .iter()
+                    .any(|(dx, dy)| y + dy >= 0 && fence(x + dx, y + dy)) =>
+            {
+                Cell::new('+').fg(Color::WHITE)
+            }
+            // inside of a region
+            _ => Cell::new(' ').bg(color(px, py)),
+        }
+    })
+}
+
+// a number for every plot, the same for all plots of a region
+fn regions(garden: &Garden) -> Grid<usize> {
+    let mut regions: Grid<Option<usize>> = Grid::filled(garden.width(), garden.height(), None);
+    let mut count = 0;
+    let mut stack: Vec<Pos> = Vec::with_capacity(garden.len());
+    for start in garden.positions() {
+        if regions[start].is_some() {
+            continue;
+        }
+        regions[start] = Some(count);
+        stack.push(start);
+        while let Some(pos) = stack.pop() {
+            for next in garden.neighbours4(pos) {
+                if garden[next] == garden[pos] && regions[next].is_none() {
+                    regions[next] = Some(count);
+                    stack.push(next);
+                }
+            }
+        }
+        count += 1;
+    }
+    regions.map(|region| region.unwrap_or_default())
+}
+
+pub fn fence_discount_price(garden: &Garden) -> u32 {
+    let mut visited: HashSet<Pos> = HashSet::with_capacity(garden.len());
+
+    let mut price = 0;
+    for (x, y) in garden.positions() {
+        if !visited.contains(&(x, y)) {
+            price += area_discount_price(garden, &mut visited, x, y);
+        }
+    }
+    price
+}
+
+fn area_discount_price(garden: &Garden, visited: &mut HashSet<Pos>, x: usize, y: usize) -> u32 {
+    let mut stack: Vec<Pos> = Vec::with_capacity(garden.len());
+    let mut area_tiles: HashSet<Pos> = HashSet::with_capacity(garden.len());
+    let mut area: u32 = 0;
+    stack.push((x, y));
+    visited.insert((x, y));
+    area_tiles.insert((x, y));
+
+    let crop = garden[(x, y)];
+
+    while let Some(pos) = stack.pop() {
+        area += 1;
+        for next in garden.neighbours4(pos).filter(|next| garden[*next] == crop) {
+            if visited.insert(next) {
+                stack.push(next);
+                area_tiles.insert(next);
+            }
+        }
+    }
+
+    let mut perimeter = 0;
+    let mut visited: HashSet<Pos> = HashSet::with_capacity(area_tiles.len());
+    for (x, y) in area_tiles.iter().copied() {
+        if visited.contains(&(x, y)) || y > 0 && area_tiles.contains(&(x, y - 1)) {
+            continue;
+        }
+        perimeter += 1;
+        let mut x2 = x;
+        while area_tiles.contains(&(x2, y))
+            && !(y > 0 && area_tiles.contains(&(x2, y - 1)))
+            && !visited.contains(&(x2, y))
+        {
+            visited.insert((x2, y));
+            if x2 == 0 {
+                break;
+            }
+            x2 -= 1;
+        }
+        x2 = x + 1;
+        while area_tiles.contains(&(x2, y))
+            && !(y > 0 && area_tiles.contains(&(x2, y - 1)))
+            && !visited.contains(&(x2, y))
+        {
+            visited.insert((x2, y));
+            x2 += 1;
+        }
+    }
+    visited.clear();
+    for (x, y) in area_tiles.iter().copied() {
+        if visited.contains(&(x, y)) || area_tiles.contains(&(x, y + 1)) {
+            continue;
+        }
+        perimeter += 1;
+        let mut x2 = x;
+        while area_tiles.contains(&(x2, y))
+            && !area_tiles.contains(&(x2, y + 1))
+            && !visited.contains(&(x2, y))
+        {
+            visited.insert((x2, y));
+            if x2 == 0 {
+                break;
+            }
+            x2 -= 1;
+        }
+        x2 = x + 1;
+        while area_tiles.contains(&(x2, y))
+            && !area_tiles.contains(&(x2, y + 1))
+            && !visited.contains(&(x2, y))
+        {
+            visited.insert((x2, y));
+            x2 += 1;
+        }
+    }
+    visited.clear();
+    for (x, y) in area_tiles.iter().copied() {
+        if visited.contains(&(x, y)) || x > 0 && area_tiles.contains(&(x - 1, y)) {
+            continue;
+        }
+        perimeter += 1;
+        let mut y2 = y;
+        while area_tiles.contains(&(x, y2))
+            && !(x > 0 && area_tiles.contains(&(x - 1, y2)))
+            && !visited.contains(&(x, y2))
+        {
+            visited.insert((x, y2));
+            if y2 == 0 {
+                break;
+            }
+            y2 -= 1;
+        }
+        y2 = y + 1;
+        while area_tiles.contains(&(x, y2))
+            && !(x > 0 && area_tiles.contains(&(x - 1, y2)))
+            && !visited.contains(&(x, y2))
+        {
+            visited.insert((x, y2));
+            y2 += 1;
+        }
+    }
+    visited.clear();
+    for (x, y) in area_tiles.iter().copied() {
+        if visited.contains(&(x, y)) || area_tiles.contains(&(x + 1, y)) {
+            continue;
+        }
+        perimeter += 1;
+        let mut y2 = y;
+        while area_tiles.contains(&(x, y2))
+            && !area_tiles.contains(&(x + 1, y2))
+            && !visited.contains(&(x, y2))
+        {
+            visited.insert((x, y2));
+            if y2 == 0 {
+                break;
+            }
+            y2 -= 1;
+        }
+        y2 = y + 1;
+        while area_tiles.contains(&(x, y2))
+            && !area_tiles.contains(&(x + 1, y2))
+            && !visited.contains(&(x, y2))
+        {
+            visited.insert((x, y2));
+            y2 += 1;
+        }
+    }
+
+    area * perimeter
+}
+
+pub fn fence_price(garden: &Garden) -> u32 {
+    let mut visited: HashSet<Pos> = HashSet::with_capacity(garden.len());
+
+    let mut price = 0;
+    for (x, y) in garden.positions() {
+        if !visited.contains(&(x, y)) {
+            price += area_price(garden, &mut visited, x, y);
+        }
+    }
+    price
+}
+
+fn area_price(garden: &Garden, visited: &mut HashSet<Pos>, x: usize, y: usize) -> u32 {
+    let mut stack: Vec<Pos> = Vec::with_capacity(garden.len());
+    let mut area: u32 = 0;
+    let mut perimeter: u32 = 0;
+    stack.push((x, y));
+    visited.insert((x, y));
+
+    let crop = garden[(x, y)];
+
+    while let Some(pos) = stack.pop() {
+        area += 1;
+        // every side without a neighbouring plot of the same crop needs a fence
+        perimeter += 4;
+        for next in garden.neighbours4(pos).filter(|next| garden[*next] == crop) {
+            perimeter -= 1;
+            if visited.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+
+    area * perimeter
+}
+
+pub type Garden = Grid<char>;
+
+pub fn parse(input: &str) -> Result<Garden, ParseError> {
+    Grid::parse(input, "a plant type", |c, _| Some(c))
+}
+
+// A square garden of regions. Plants start out random and are then mostly copied from their
+// neighbours a few times, which clumps them into regions of all kinds of shapes (including
+// regions inside of other regions).
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut garden: Garden = Grid::from_fn(size, size, |_| char::from(b'A' + rng.below(26) as u8));
+    let mut positions: Box<[Pos]> = garden.positions().collect();
+    for _ in 0..4 {
+        rng.shuffle(&mut positions);
+        for pos in positions.iter() {
+            let neighbours: Vec<Pos> = garden.neighbours4(*pos).collect();
+            if !neighbours.is_empty() && rng.chance(7, 8) {
+                garden[*pos] = garden[*rng.choose(&neighbours)];
+            }
+        }
+    }
+    garden.render(|plant| *plant)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SMALL_EXAMPLE: &str = r#"AAAA
+BBCD
+BBCC
+EEEC
+"#;
+
+    const ENCLAVE_EXAMPLE: &str = r#"OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO
+"#;
+
+    const EXAMPLE: &str = r#"RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+"#;
+
+    const E_EXAMPLE: &str = r#"EEEEE
+EXXXX
+EEEEE
+EXXXX
+EEEEE
+"#;
+
+    const AB_EXAMPLE: &str = r#"AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA
+"#;
+
+    #[test]
+    fn fence_picture_draws_fences_between_regions() {
+        // given
+        let garden = parse(SMALL_EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let picture = fence_picture(&garden);
+
+        // then
+        assert_eq!(
+            picture.to_string(),
+            "+-+-+-+-+\n\
+             |A A A A|\n\
+             +-+-+-+-+\n\
+             |B B|C|D|\n\
+             +   + +-+\n\
+             |B B|C C|\n\
+             +-+-+-+ +\n\
+             |E E E|C|\n\
+             +-+-+-+-+\n"
+        );
+    }
+
+    #[test]
+    fn fence_price_works_for_small_example() {
+        // given
+        let garden = parse(SMALL_EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let price = fence_price(&garden);
+
+        // then
+        assert_eq!(price, 140);
+    }
+
+    #[test]
+    fn fence_price_works_for_enclave_example() {
+        // given
+        let garden = parse(ENCLAVE_EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let price = fence_price(&garden);
+
+        // then
+        assert_eq!(price, 772);
+    }
+
+    #[test]
+    fn fence_price_works_for_example() {
+        // given
+        let garden = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let price = fence_price(&garden);
+
+        // then
+        assert_eq!(price, 1930);
+    }
+
+    #[test]
+    fn fence_discount_price_works_for_small_example() {
+        // given
+        let garden = parse(SMALL_EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let price = fence_discount_price(&garden);
+
+        // then
+        assert_eq!(price, 80);
+    }
+
+    #[test]
+    fn fence_discount_price_works_for_enclave_example() {
+        // given
+        let garden = parse(ENCLAVE_EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let price = fence_discount_price(&garden);
+
+        // then
+        assert_eq!(price, 436);
+    }
+
+    #[test]
+    fn fence_discount_price_works_for_e_example() {
+        // given
+        let garden = parse(E_EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let price = fence_discount_price(&garden);
+
+        // then
+        assert_eq!(price, 236);
+    }
+
+    #[test]
+    fn fence_discount_price_works_for_ab_example() {
+        // given
+        let garden = parse(AB_EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let price = fence_discount_price(&garden);
+
+        // then
+        assert_eq!(price, 368);
+    }
+
+    #[test]
+    fn fence_discount_price_works_for_example() {
+        // given
+        let garden = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let price = fence_discount_price(&garden);
+
+        // then
+        assert_eq!(price, 1206);
+    }
+}
@@ -0,0 +1,292 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{expect_prefix, expect_split, par_map, parse_number, Answer, ParseError, Solution};
+use std::fmt::Write;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input<'a> = Box<[Machine]>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(min_tokens_for_all_prizes(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(min_tokens_for_all_prizes(&fix_machines(input)).into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(320)))
+    }
+}
+
+pub fn fix_machines(machines: &[Machine]) -> Box<[Machine]> {
+    machines
+        .iter()
+        .map(|broken| Machine {
+            prize_x: broken.prize_x + 10_000_000_000_000,
+            prize_y: broken.prize_y + 10_000_000_000_000,
+            ..*broken
+        })
+        .collect()
+}
+
+pub fn min_tokens_for_all_prizes(machines: &[Machine]) -> i64 {
+    par_map(machines, find_cheapest_solution)
+        .into_iter()
+        .flatten()
+        .sum()
+}
+
+fn find_cheapest_solution(machine: &Machine) -> Option<i64> {
+    let (a, b) = find_presses(machine)?;
+    Some(3 * a + b)
+}
+
+// the presses of button A and B that reach the prize with the fewest tokens
+fn find_presses(machine: &Machine) -> Option<(i64, i64)> {
+    // Today is math day. I used Pen & Paper to get these equations
+    let num = machine.prize_y * machine.a_x - machine.a_y * machine.prize_x;
+    let denom = machine.b_y * machine.a_x - machine.a_y * machine.b_x;
+    if denom == 0 {
+        return find_presses_for_parallel_buttons(machine);
+    }
+    if num % denom != 0 {
+        return None;
+    }
+    let b = num / denom;
+    if b < 0 {
+        return None;
+    }
+    let a = find_a_presses(machine, b)?;
+
+    Some((a, b))
+}
+
+// If both buttons move in the same direction, there may be many ways to reach the prize (or
+// none). All of them are solutions of a*a_x + b*b_x = prize_x, which are
+// a = a0 + k*b_x/g and b = b0 - k*a_x/g for the gcd g. The cost changes linearly in k, so the
+// cheapest solution has either as few A or as few B presses as possible.
+fn find_presses_for_parallel_buttons(machine: &Machine) -> Option<(i64, i64)> {
+    let m = machine;
+    if m.a_x <= 0 || m.b_x <= 0 || m.prize_y * m.a_x != m.a_y * m.prize_x {
+        return None;
+    }
+    let (g, x0, y0) = extended_gcd(m.a_x as i128, m.b_x as i128);
+    let prize = m.prize_x as i128;
+    if prize % g != 0 {
+        return None;
+    }
+    let (a0, b0) = (x0 * prize / g, y0 * prize / g);
+    let (step_a, step_b) = (m.b_x as i128 / g, m.a_x as i128 / g);
+    // a >= 0 and b >= 0
+    let min_k = -(a0.div_euclid(step_a));
+    let max_k = b0.div_euclid(step_b);
+    if min_k > max_k {
+        return None;
+    }
+    let k = if 3 * step_a > step_b { min_k } else { max_k };
+    let (a, b) = (a0 + k * step_a, b0 - k * step_b);
+    Some((a.try_into().ok()?, b.try_into().ok()?))
+}
+
+// (g, x, y) with a*x + b*y = g = gcd(a, b)
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+fn find_a_presses(machine: &Machine, b: i64) -> Option<i64> {
+    let x = machine.b_x * b;
+    if x > machine.prize_x {
+        return None;
+    }
+    let dx = machine.prize_x - x;
+    let a = dx / machine.a_x;
+    if x + a * machine.a_x == machine.prize_x
+        && machine.a_y * a + machine.b_y * b == machine.prize_y
+    {
+        Some(a)
+    } else {
+        None
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Machine {
+    a_x: i64,
+    a_y: i64,
+    b_x: i64,
+    b_y: i64,
+    prize_x: i64,
+    prize_y: i64,
+}
+
+pub fn parse(input: &str) -> Result<Box<[Machine]>, ParseError> {
+    input.split("\n\n").map(parse_machine).collect()
+}
+
+fn parse_machine(block: &str) -> Result<Machine, ParseError> {
+    let mut lines = block.lines();
+    let mut next_line = || {
+        lines
+            .next()
+            .ok_or_else(|| ParseError::after(block, "three lines per machine"))
+    };
+    let (a_x, a_y) = parse_line(next_line()?, "Button A: ", "+")?;
+    let (b_x, b_y) = parse_line(next_line()?, "Button B: ", "+")?;
+    let (prize_x, prize_y) = parse_line(next_line()?, "Prize: ", "=")?;
+
+    Ok(Machine {
+        a_x,
+        a_y,
+        b_x,
+        b_y,
+        prize_x,
+        prize_y,
+    })
+}
+
+// parses lines like "Button A: X+94, Y+34" or "Prize: X=8400, Y=5400"
+fn parse_line(line: &str, prefix: &str, sign: &str) -> Result<(i64, i64), ParseError> {
+    let (x, y) = expect_split(expect_prefix(line, prefix)?, ", ")?;
+    let x = parse_number(expect_prefix(x, &format!("X{sign}"))?)?;
+    let y = parse_number(expect_prefix(y, &format!("Y{sign}"))?)?;
+    Ok((x, y))
+}
+
+// Claw machines with buttons moving 10 to 99 steps in either direction. About half of the prizes
+// can be won, their position is the result of pressing the buttons up to 100 times each.
+pub fn generate(rng: &mut Rng, machines: usize) -> String {
+    let mut input = String::with_capacity(machines * 64);
+    for i in 0..machines {
+        if i > 0 {
+            input.push('\n');
+        }
+        let (a_x, a_y) = (rng.range(10..100), rng.range(10..100));
+        let (b_x, b_y) = (rng.range(10..100), rng.range(10..100));
+        let (prize_x, prize_y) = if rng.chance(1, 2) {
+            let (a, b) = (rng.range(1..101), rng.range(1..101));
+            (a * a_x + b * b_x, a * a_y + b * b_y)
+        } else {
+            (rng.range(1000..20000), rng.range(1000..20000))
+        };
+        let _ = writeln!(input, "Button A: X+{a_x}, Y+{a_y}");
+        let _ = writeln!(input, "Button B: X+{b_x}, Y+{b_y}");
+        let _ = writeln!(input, "Prize: X={prize_x}, Y={prize_y}");
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::random::check_property;
+
+    const EXAMPLE: &str = r#"Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+"#;
+
+    #[test]
+    fn min_token_for_all_prizes_works_for_example() {
+        // given
+        let machines = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let price = min_tokens_for_all_prizes(&machines);
+
+        // then
+        assert_eq!(price, 480);
+    }
+
+    #[test]
+    fn parse_reports_location_of_malformed_value() {
+        // given
+        let input = EXAMPLE.replace("X=7870", "X=78a0");
+
+        // when
+        let error = common::parse::<Day13>(&input).expect_err("expected input to be rejected");
+
+        // then
+        let location = error
+            .location
+            .as_ref()
+            .expect("expected error to have a location");
+        assert_eq!(location.line, 11);
+        assert_eq!(location.column, 10);
+        assert_eq!(error.snippet, "78a0");
+    }
+
+    #[test]
+    fn find_presses_reaches_the_prize() {
+        check_property(2000, |rng| {
+            // given
+            let (a_x, a_y) = (rng.range(1..100), rng.range(1..100));
+            // parallel buttons are rare in random input, so make some on purpose
+            let (b_x, b_y) = if rng.chance(1, 4) {
+                let factor = rng.range(1..4);
+                (a_x * factor, a_y * factor)
+            } else {
+                (rng.range(1..100), rng.range(1..100))
+            };
+            let limit = if rng.chance(1, 2) {
+                100
+            } else {
+                100_000_000_000
+            };
+            let (a, b) = (rng.range(0..limit), rng.range(0..limit));
+            let machine = Machine {
+                a_x,
+                a_y,
+                b_x,
+                b_y,
+                prize_x: a * a_x + b * b_x,
+                prize_y: a * a_y + b * b_y,
+            };
+
+            // when
+            let presses = find_presses(&machine);
+
+            // then
+            let Some((found_a, found_b)) = presses else {
+                return Err(format!("no solution found for {machine:?}"));
+            };
+            if found_a < 0
+                || found_b < 0
+                || found_a * a_x + found_b * b_x != machine.prize_x
+                || found_a * a_y + found_b * b_y != machine.prize_y
+            {
+                return Err(format!("{found_a}×A + {found_b}×B misses {machine:?}"));
+            }
+            if 3 * found_a + found_b > 3 * a + b {
+                return Err(format!("{a}×A + {b}×B is cheaper for {machine:?}"));
+            }
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,228 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{
+    expect_prefix, expect_split, parse_number, Animation, Answer, Cell, Color, Frame, ParseError,
+    Picture, Solution,
+};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+const WIDTH: i64 = 101;
+const HEIGHT: i64 = 103;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input<'a> = Box<[Robot]>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(safety_factor_after_time(input, 100, WIDTH, HEIGHT).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let tree_time = find_tree_config(input, WIDTH, HEIGHT).ok_or(NO_TREE)?;
+        Ok(tree_time.into())
+    }
+
+    fn auxiliary_output(input: &Self::Input<'_>) -> Result<Option<String>, String> {
+        let tree_time = find_tree_config(input, WIDTH, HEIGHT).ok_or(NO_TREE)?;
+        let robot_pos = robot_positions(input, tree_time, WIDTH, HEIGHT);
+        Ok(Some(format!(
+            "Possible tree config after {tree_time} seconds. Please double-check the image below.\n{}",
+            render_robots(&robot_pos, WIDTH, HEIGHT)
+        )))
+    }
+
+    fn animation<'a>(input: &'a Self::Input<'_>) -> Option<Animation<'a>> {
+        Some(Box::new(robot_frames(input, WIDTH, HEIGHT)))
+    }
+
+    fn picture(input: &Self::Input<'_>) -> Result<Option<Picture>, String> {
+        let tree_time = find_tree_config(input, WIDTH, HEIGHT).ok_or(NO_TREE)?;
+        let robot_pos = robot_positions(input, tree_time, WIDTH, HEIGHT);
+        Ok(Some(robot_picture(&robot_pos, WIDTH, HEIGHT)))
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(500)))
+    }
+}
+
+const NO_TREE: &str = "the robots never line up to a tree";
+
+pub fn safety_factor_after_time(robots: &[Robot], t: i64, width: i64, height: i64) -> u64 {
+    let (q1, q2, q3, q4) = robots
+        .iter()
+        .copied()
+        .map(|robot| move_robot(robot, t, width, height))
+        .fold(
+            (0u64, 0u64, 0u64, 0u64),
+            |(mut q1, mut q2, mut q3, mut q4), robot| {
+                match (robot.px.cmp(&(width / 2)), robot.py.cmp(&(height / 2))) {
+                    (Ordering::Less, Ordering::Less) => q1 += 1,
+                    (Ordering::Less, Ordering::Greater) => q2 += 1,
+                    (Ordering::Greater, Ordering::Less) => q3 += 1,
+                    (Ordering::Greater, Ordering::Greater) => q4 += 1,
+                    _ => (),
+                }
+                (q1, q2, q3, q4)
+            },
+        );
+    q1 * q2 * q3 * q4
+}
+
+fn move_robot(robot: Robot, t: i64, width: i64, height: i64) -> Robot {
+    // in i128, so large positions or velocities cannot overflow
+    let px = (robot.px as i128 + t as i128 * robot.vx as i128).rem_euclid(width as i128) as i64;
+    let py = (robot.py as i128 + t as i128 * robot.vy as i128).rem_euclid(height as i128) as i64;
+    Robot { px, py, ..robot }
+}
+
+pub fn robot_positions(robots: &[Robot], t: i64, width: i64, height: i64) -> HashSet<(i64, i64)> {
+    robots
+        .iter()
+        .map(|robot| move_robot(*robot, t, width, height))
+        .map(|robot| (robot.px, robot.py))
+        .collect()
+}
+
+pub fn robot_picture(robot_pos: &HashSet<(i64, i64)>, width: i64, height: i64) -> Picture {
+    Picture::from_fn(width as usize, height as usize, |(x, y)| {
+        if robot_pos.contains(&(x as i64, y as i64)) {
+            Cell::new('R').fg(Color::GREEN)
+        } else {
+            Cell::new(' ')
+        }
+    })
+}
+
+pub fn render_robots(robot_pos: &HashSet<(i64, i64)>, width: i64, height: i64) -> String {
+    robot_picture(robot_pos, width, height).to_string()
+}
+
+// One frame per second until the positions repeat, so the tree can be spotted by eye as well.
+pub fn robot_frames(robots: &[Robot], width: i64, height: i64) -> impl Iterator<Item = Frame> + '_ {
+    (0..width * height).map(move |time| {
+        let robot_pos = robot_positions(robots, time, width, height);
+        let caption = if n_robots_in_a_row(&robot_pos, 30) {
+            format!("after {time} seconds, a possible tree")
+        } else {
+            format!("after {time} seconds")
+        };
+        Frame {
+            picture: robot_picture(&robot_pos, width, height),
+            caption,
+        }
+    })
+}
+
+fn n_robots_in_a_row(robot_pos: &HashSet<(i64, i64)>, n: i64) -> bool {
+    robot_pos
+        .iter()
+        .any(|(px, py)| (1..=n).all(|dx| robot_pos.contains(&(px + dx, *py))))
+}
+
+// The positions repeat after width × height seconds at the latest, so there is no need to look
+// any further.
+pub fn find_tree_config(robots: &[Robot], width: i64, height: i64) -> Option<i64> {
+    (0..width * height)
+        .find(|time| n_robots_in_a_row(&robot_positions(robots, *time, width, height), 30))
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Robot {
+    px: i64,
+    py: i64,
+    vx: i64,
+    vy: i64,
+}
+
+pub fn parse(input: &str) -> Result<Box<[Robot]>, ParseError> {
+    input.lines().map(parse_robot).collect()
+}
+
+fn parse_robot(line: &str) -> Result<Robot, ParseError> {
+    let (p, v) = expect_split(line, " ")?;
+    let (px, py) = parse_pair(expect_prefix(p, "p=")?)?;
+    let (vx, vy) = parse_pair(expect_prefix(v, "v=")?)?;
+    Ok(Robot { px, py, vx, vy })
+}
+
+fn parse_pair(s: &str) -> Result<(i64, i64), ParseError> {
+    let (x, y) = expect_split(s, ",")?;
+    Ok((parse_number(x)?, parse_number(y)?))
+}
+
+// the outline of the picture frame around the tree in the real input
+const FRAME_WIDTH: i64 = 31;
+const FRAME_HEIGHT: i64 = 33;
+
+// Robots (at least 31) moving around the real area. At a random time a part of them lines up to
+// the frame of a picture, or only to its top row if there are not enough robots for all of it.
+pub fn generate(rng: &mut Rng, robots: usize) -> String {
+    let tree_time = rng.range(0..WIDTH * HEIGHT);
+    let (left, top) = (
+        rng.range(0..WIDTH - FRAME_WIDTH),
+        rng.range(0..HEIGHT - FRAME_HEIGHT),
+    );
+    let mut frame: Vec<(i64, i64)> = (0..FRAME_WIDTH).map(|x| (left + x, top)).collect();
+    if robots >= 4 * FRAME_HEIGHT as usize {
+        frame.extend((0..FRAME_WIDTH).map(|x| (left + x, top + FRAME_HEIGHT - 1)));
+        frame.extend((1..FRAME_HEIGHT - 1).map(|y| (left, top + y)));
+        frame.extend((1..FRAME_HEIGHT - 1).map(|y| (left + FRAME_WIDTH - 1, top + y)));
+    }
+
+    let mut input = String::with_capacity(robots.max(frame.len()) * 20);
+    for i in 0..robots.max(frame.len()) {
+        let (vx, vy) = (rng.range(-99..100), rng.range(-99..100));
+        let (px, py) = match frame.get(i) {
+            // where the robot has to start to be at its place in the frame at the tree time
+            Some((x, y)) => (
+                (x - tree_time * vx).rem_euclid(WIDTH),
+                (y - tree_time * vy).rem_euclid(HEIGHT),
+            ),
+            None => (rng.range(0..WIDTH), rng.range(0..HEIGHT)),
+        };
+        let _ = writeln!(input, "p={px},{py} v={vx},{vy}");
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE: &str = r#"p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+"#;
+
+    #[test]
+    fn safety_factor_after_time_works_for_example() {
+        // given
+        let robots = parse(EXAMPLE).expect("expected example to parse");
+
+        // when
+        let sf = safety_factor_after_time(&robots, 100, 11, 7);
+
+        // then
+        assert_eq!(sf, 12);
+    }
+}
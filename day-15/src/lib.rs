@@ -0,0 +1,414 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{Animation, Answer, Cell, Color, Frame, ParseError, Solution};
+use grid::{Grid, Pos};
+use std::collections::HashSet;
+use std::iter::once;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input<'a> = (Warehouse, Instructions, Pos);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (warehouse, instructions, start) = input;
+        let warehouse = apply_instructions(warehouse.clone(), instructions, *start);
+        Ok(gps_sum(&warehouse).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (warehouse, instructions, (start_x, start_y)) = input;
+        let warehouse = apply_instructions_wide(
+            widen_warehouse(warehouse),
+            instructions,
+            (start_x * 2, *start_y),
+        );
+        Ok(gps_sum_wide(&warehouse).into())
+    }
+
+    fn animation<'a>(input: &'a Self::Input<'_>) -> Option<Animation<'a>> {
+        let (warehouse, instructions, start) = input;
+        Some(Box::new(wide_frames(warehouse, instructions, *start)))
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(50)))
+    }
+}
+
+// The robot pushing boxes through the wide warehouse of part 2, one frame per instruction.
+pub fn wide_frames<'a>(
+    warehouse: &Warehouse,
+    instructions: &'a [Dir],
+    (start_x, start_y): Pos,
+) -> impl Iterator<Item = Frame> + 'a {
+    let mut warehouse = widen_warehouse(warehouse);
+    let mut robot = (start_x * 2, start_y);
+    let first = wide_frame(&warehouse, robot, "before the first move".to_owned());
+    once(first).chain(instructions.iter().enumerate().map(move |(i, dir)| {
+        move_robot_wide(&mut warehouse, &mut robot, *dir);
+        let caption = format!("move {} of {}: {}", i + 1, instructions.len(), dir.symbol());
+        wide_frame(&warehouse, robot, caption)
+    }))
+}
+
+fn wide_frame(warehouse: &WideWarehouse, robot: Pos, caption: String) -> Frame {
+    let mut picture = warehouse.picture(|tile| match tile {
+        WideTile::Empty => Cell::new('.').fg(Color::DARK_GRAY),
+        WideTile::Wall => Cell::new('#').fg(Color::GRAY),
+        WideTile::LBox => Cell::new('[').fg(Color::YELLOW),
+        WideTile::RBox => Cell::new(']').fg(Color::YELLOW),
+    });
+    picture.set(robot, Cell::new('@').fg(Color::WHITE).bg(Color::RED));
+    Frame { picture, caption }
+}
+
+pub fn apply_instructions_wide(
+    mut warehouse: WideWarehouse,
+    instructions: &[Dir],
+    start: Pos,
+) -> WideWarehouse {
+    let mut robot = start;
+    for dir in instructions {
+        move_robot_wide(&mut warehouse, &mut robot, *dir);
+    }
+    warehouse
+}
+
+fn move_robot_wide(warehouse: &mut WideWarehouse, robot: &mut Pos, dir: Dir) {
+    let moved_tiles: HashSet<(Pos, WideTile)> = if matches!(dir, Dir::Left | Dir::Right) {
+        let mut moved: HashSet<(Pos, WideTile)> = HashSet::with_capacity(64);
+        let mut pos = dir.step(*robot);
+        loop {
+            match warehouse[pos] {
+                WideTile::Wall => {
+                    return;
+                }
+                WideTile::Empty => {
+                    break;
+                }
+                tile => {
+                    moved.insert((pos, tile));
+                    pos = dir.step(pos);
+                }
+            }
+        }
+        moved
+    } else {
+        let mut seen: HashSet<(Pos, WideTile)> = HashSet::with_capacity(128);
+        let mut stack: Vec<Pos> = Vec::with_capacity(128);
+        stack.push(dir.step(*robot));
+        while let Some((x, y)) = stack.pop() {
+            let tile = warehouse[(x, y)];
+            if seen.contains(&((x, y), tile)) {
+                continue;
+            }
+            match tile {
+                WideTile::Wall => {
+                    return;
+                }
+                WideTile::Empty => {
+                    continue;
+                }
+                WideTile::RBox => {
+                    seen.insert(((x, y), tile));
+                    stack.push((x - 1, y));
+                    stack.push(dir.step((x, y)));
+                }
+                WideTile::LBox => {
+                    seen.insert(((x, y), tile));
+                    stack.push((x + 1, y));
+                    stack.push(dir.step((x, y)));
+                }
+            }
+        }
+        seen
+    };
+    *robot = dir.step(*robot);
+    for (pos, _) in moved_tiles.iter().copied() {
+        warehouse[pos] = WideTile::Empty;
+    }
+    for (pos, tile) in moved_tiles.iter().copied() {
+        warehouse[dir.step(pos)] = tile;
+    }
+}
+
+pub fn gps_sum_wide(warehouse: &WideWarehouse) -> usize {
+    warehouse
+        .iter()
+        .filter(|(_, tile)| **tile == WideTile::LBox)
+        .map(|((x, y), _)| x + 100 * y)
+        .sum()
+}
+
+pub fn gps_sum(warehouse: &Warehouse) -> usize {
+    warehouse
+        .iter()
+        .filter(|(_, tile)| **tile == Tile::Box)
+        .map(|((x, y), _)| x + 100 * y)
+        .sum()
+}
+
+pub fn apply_instructions(mut warehouse: Warehouse, instructions: &[Dir], start: Pos) -> Warehouse {
+    let mut robot = start;
+    for dir in instructions {
+        move_robot(&mut warehouse, &mut robot, *dir);
+    }
+    warehouse
+}
+
+fn move_robot(warehouse: &mut Warehouse, robot: &mut Pos, dir: Dir) {
+    let mut pos = dir.step(*robot);
+    while warehouse[pos] == Tile::Box {
+        pos = dir.step(pos);
+    }
+    if warehouse[pos] == Tile::Wall {
+        return;
+    }
+    *robot = dir.step(*robot);
+    warehouse.swap(*robot, pos);
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum WideTile {
+    Empty,
+    Wall,
+    LBox,
+    RBox,
+}
+
+pub type WideWarehouse = Grid<WideTile>;
+
+pub fn widen_warehouse(warehouse: &Warehouse) -> WideWarehouse {
+    let tiles: Box<[WideTile]> = warehouse
+        .iter()
+        .flat_map(|(_, tile)| match tile {
+            Tile::Empty => [WideTile::Empty, WideTile::Empty],
+            Tile::Wall => [WideTile::Wall, WideTile::Wall],
+            Tile::Box => [WideTile::LBox, WideTile::RBox],
+        })
+        .collect();
+
+    Grid::new(warehouse.width() * 2, warehouse.height(), tiles)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Box,
+}
+
+pub type Warehouse = Grid<Tile>;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    // the wall around the warehouse makes sure we never step outside of it
+    fn step(self, (x, y): Pos) -> Pos {
+        match self {
+            Dir::Up => (x, y - 1),
+            Dir::Right => (x + 1, y),
+            Dir::Down => (x, y + 1),
+            Dir::Left => (x - 1, y),
+        }
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            Dir::Up => '^',
+            Dir::Right => '>',
+            Dir::Down => 'v',
+            Dir::Left => '<',
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<(Warehouse, Instructions, Pos), ParseError> {
+    let (map, inst) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::after(input, "an empty line between the map and the instructions")
+    })?;
+    let (warehouse, start) = parse_warehouse(map)?;
+    let inst = parse_instructions(inst)?;
+
+    Ok((warehouse, inst, start))
+}
+
+fn parse_warehouse(map: &str) -> Result<(Warehouse, Pos), ParseError> {
+    let mut robot: Option<Pos> = None;
+    let warehouse = Grid::parse(map, "'.', '#', 'O' or '@'", |c, pos| match c {
+        '.' => Some(Tile::Empty),
+        '#' => Some(Tile::Wall),
+        'O' => Some(Tile::Box),
+        '@' => {
+            robot.get_or_insert(pos);
+            Some(Tile::Empty)
+        }
+        _ => None,
+    })?;
+    if let Some((pos, _)) = warehouse
+        .iter()
+        .find(|(pos, tile)| warehouse.is_border(*pos) && **tile != Tile::Wall)
+    {
+        return Err(grid::error_at(map, pos, "a wall ('#') at the border"));
+    }
+    let robot =
+        robot.ok_or_else(|| ParseError::after(map, "a robot ('@') somewhere in the map"))?;
+
+    Ok((warehouse, robot))
+}
+
+pub type Instructions = Box<[Dir]>;
+
+fn parse_instructions(input: &str) -> Result<Instructions, ParseError> {
+    input
+        .char_indices()
+        .filter(|(_, c)| *c != '\n')
+        .map(|(i, c)| match c {
+            '^' => Ok(Dir::Up),
+            '>' => Ok(Dir::Right),
+            'v' => Ok(Dir::Down),
+            '<' => Ok(Dir::Left),
+            _ => Err(ParseError::at_char(input, i, "'^', '>', 'v' or '<'")),
+        })
+        .collect()
+}
+
+// A square warehouse with walls around it, some walls and a lot of boxes inside, followed by
+// random moves of the robot in lines of 1000 (8 moves per tile of the warehouse).
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(3);
+    let mut map = Grid::from_fn(size, size, |_| match rng.below(20) {
+        0 | 1 => '#',
+        2..=6 => 'O',
+        _ => '.',
+    });
+    for pos in map.positions() {
+        if map.is_border(pos) {
+            map[pos] = '#';
+        }
+    }
+    let robot = (rng.range_usize(1..size - 1), rng.range_usize(1..size - 1));
+    map[robot] = '@';
+
+    let moves = size * size * 8;
+    let mut input = map.render(|tile| *tile);
+    input.reserve(moves + moves / 1000 + 2);
+    for i in 0..moves {
+        if i % 1000 == 0 {
+            input.push('\n');
+        }
+        input.push(*rng.choose(&['^', '>', 'v', '<']));
+    }
+    input.push('\n');
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SMALL_EXAMPLE: &str = r#"########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+"#;
+
+    const EXAMPLE: &str = r#"##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+"#;
+
+    #[test]
+    fn apply_instructions_works_for_small_example() {
+        // given
+        let (warehouse, instructions, start) =
+            parse(SMALL_EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let warehouse = apply_instructions(warehouse, &instructions, start);
+        let result = gps_sum(&warehouse);
+
+        // then
+        assert_eq!(result, 2028);
+    }
+
+    #[test]
+    fn apply_instructions_wide_works_for_example() {
+        // given
+        let (warehouse, instructions, (start_x, start_y)) =
+            parse(EXAMPLE).expect("expected example input to parse");
+        let warehouse = widen_warehouse(&warehouse);
+
+        // when
+        let warehouse = apply_instructions_wide(warehouse, &instructions, (start_x * 2, start_y));
+        let result = gps_sum_wide(&warehouse);
+
+        // then
+        assert_eq!(result, 9021);
+    }
+
+    #[test]
+    fn wide_frames_show_every_move() {
+        // given
+        let (warehouse, instructions, start) =
+            parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let frames: Vec<Frame> = wide_frames(&warehouse, &instructions, start).collect();
+
+        // then
+        assert_eq!(frames.len(), instructions.len() + 1);
+        let last = frames.last().expect("expected at least one frame");
+        assert_eq!(
+            last.picture.to_string(),
+            "####################\n\
+             ##[].......[].[][]##\n\
+             ##[]...........[].##\n\
+             ##[]........[][][]##\n\
+             ##[]......[]....[]##\n\
+             ##..##......[]....##\n\
+             ##..[]............##\n\
+             ##..@......[].[][]##\n\
+             ##......[][]..[]..##\n\
+             ####################\n"
+        );
+    }
+}
@@ -0,0 +1,286 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{Animation, Answer, Cell, Color, Frame, ParseError, Picture, Solution};
+use grid::{Grid, Pos};
+use search::{dijkstra, Cost, Search};
+use std::collections::HashSet;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input<'a> = (Maze, V2, V2);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (maze, start, goal) = input;
+        let (score, _) = winning_score(maze, *start, *goal).ok_or_else(no_path)?;
+        Ok(score.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (maze, start, goal) = input;
+        let (_, winning_path_tiles) = winning_score(maze, *start, *goal).ok_or_else(no_path)?;
+        Ok(winning_path_tiles.into())
+    }
+
+    fn animation<'a>(input: &'a Self::Input<'_>) -> Option<Animation<'a>> {
+        let (maze, start, goal) = input;
+        Some(Box::new(path_frames(maze, *start, *goal)))
+    }
+
+    fn picture(input: &Self::Input<'_>) -> Result<Option<Picture>, String> {
+        let (maze, start, goal) = input;
+        let (_, path_tiles) = winning_path_tiles(maze, *start, *goal).ok_or_else(no_path)?;
+        Ok(Some(maze_picture(maze, *start, *goal, &path_tiles)))
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(141)))
+    }
+}
+
+fn no_path() -> String {
+    "There is no path to the goal.".to_owned()
+}
+
+// lowest score and the number of tiles on any path with that score
+pub fn winning_score(maze: &Maze, start: V2, goal: V2) -> Option<(Cost, usize)> {
+    let (score, path_tiles) = winning_path_tiles(maze, start, goal)?;
+    Some((score, path_tiles.len()))
+}
+
+// lowest score and the tiles on any path with that score
+pub fn winning_path_tiles(maze: &Maze, start: V2, goal: V2) -> Option<(Cost, HashSet<V2>)> {
+    let search = search_maze(maze, start, goal);
+    let path_tiles = tiles_on_best_paths(&search);
+    Some((search.cost()?, path_tiles))
+}
+
+// The reindeer following one of the best paths, with the tiles of all best paths highlighted.
+// There are no frames if the goal cannot be reached.
+pub fn path_frames(maze: &Maze, start: V2, goal: V2) -> impl Iterator<Item = Frame> + '_ {
+    let search = search_maze(maze, start, goal);
+    let path_tiles = tiles_on_best_paths(&search);
+    let path = search.path().unwrap_or_default();
+    path.into_iter().map(move |(pos, dir)| {
+        let mut picture = maze_picture(maze, start, goal, &path_tiles);
+        picture.set(pos, Cell::new(dir.symbol()).fg(Color::WHITE).bg(Color::RED));
+        let score = search.distance(&(pos, dir)).unwrap_or_default();
+        Frame {
+            picture,
+            caption: format!("score {score}"),
+        }
+    })
+}
+
+// the maze with the tiles of the best paths highlighted
+pub fn maze_picture(maze: &Maze, start: V2, goal: V2, path_tiles: &HashSet<V2>) -> Picture {
+    let mut picture = maze.picture(|tile| match tile {
+        Tile::Wall => Cell::new('#').fg(Color::GRAY),
+        Tile::Floor => Cell::new('.').fg(Color::DARK_GRAY),
+    });
+    picture.set(start, Cell::new('S').fg(Color::GREEN));
+    picture.set(goal, Cell::new('E').fg(Color::GREEN));
+    picture.highlight(path_tiles.iter().copied(), Color::BLUE);
+    picture
+}
+
+fn tiles_on_best_paths(search: &Search<State>) -> HashSet<V2> {
+    search
+        .states_on_paths_to(search.goals().iter().copied())
+        .into_iter()
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+fn search_maze(maze: &Maze, start: V2, goal: V2) -> Search<State> {
+    dijkstra(
+        [(start, Dir::East)],
+        |&((x, y), dir): &State| {
+            let neighbour = match dir {
+                Dir::East => (x + 1, y),
+                Dir::South => (x, y + 1),
+                Dir::West => (x - 1, y),
+                Dir::North => (x, y - 1),
+            };
+            let forward = (maze[neighbour] == Tile::Floor).then_some(((neighbour, dir), 1));
+            forward.into_iter().chain([
+                (((x, y), dir.rot()), 1000),
+                (((x, y), dir.rot_counter()), 1000),
+            ])
+        },
+        |(pos, _)| *pos == goal,
+    )
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+enum Dir {
+    East,
+    South,
+    West,
+    North,
+}
+
+impl Dir {
+    fn rot(self) -> Self {
+        match self {
+            Dir::East => Dir::South,
+            Dir::South => Dir::West,
+            Dir::West => Dir::North,
+            Dir::North => Dir::East,
+        }
+    }
+    fn rot_counter(self) -> Self {
+        match self {
+            Dir::East => Dir::North,
+            Dir::North => Dir::West,
+            Dir::West => Dir::South,
+            Dir::South => Dir::East,
+        }
+    }
+    fn symbol(self) -> char {
+        match self {
+            Dir::East => '>',
+            Dir::South => 'v',
+            Dir::West => '<',
+            Dir::North => '^',
+        }
+    }
+}
+
+pub type V2 = Pos;
+type State = (V2, Dir);
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Tile {
+    Wall,
+    Floor,
+}
+
+pub type Maze = Grid<Tile>;
+
+pub fn parse(input: &str) -> Result<(Maze, V2, V2), ParseError> {
+    let mut start: Option<V2> = None;
+    let mut goal: Option<V2> = None;
+    let maze = Grid::parse(input, "'#', '.', 'S' or 'E'", |c, pos| match c {
+        '#' => Some(Tile::Wall),
+        '.' => Some(Tile::Floor),
+        'S' => {
+            start.get_or_insert(pos);
+            Some(Tile::Floor)
+        }
+        'E' => {
+            goal.get_or_insert(pos);
+            Some(Tile::Floor)
+        }
+        _ => None,
+    })?;
+    if let Some((pos, _)) = maze
+        .iter()
+        .find(|(pos, tile)| maze.is_border(*pos) && **tile != Tile::Wall)
+    {
+        return Err(grid::error_at(input, pos, "a wall ('#') around the maze"));
+    }
+    let start = start.ok_or_else(|| ParseError::after(input, "a start ('S') in the maze"))?;
+    let goal = goal.ok_or_else(|| ParseError::after(input, "an end ('E') in the maze"))?;
+
+    Ok((maze, start, goal))
+}
+
+// A square maze (with an odd width of at least 5) carved by a random depth-first search, with
+// some extra walls knocked out so there are several best paths. The start is in the bottom left
+// corner and the end in the top right one, like in the real input.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(5) | 1;
+    let mut maze: Maze = Grid::filled(size, size, Tile::Wall);
+    let mut stack: Vec<V2> = vec![(1, 1)];
+    maze[(1, 1)] = Tile::Floor;
+    while let Some(&(x, y)) = stack.last() {
+        let next: Vec<V2> = [(0, -2), (2, 0), (0, 2), (-2, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy)| maze.offset((x, y), (dx, dy)))
+            .filter(|next| !maze.is_border(*next) && maze[*next] == Tile::Wall)
+            .collect();
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (nx, ny) = *rng.choose(&next);
+        maze[((x + nx) / 2, (y + ny) / 2)] = Tile::Floor;
+        maze[(nx, ny)] = Tile::Floor;
+        stack.push((nx, ny));
+    }
+    for _ in 0..size * size / 50 {
+        // walls between two corridors have one odd and one even coordinate
+        let (x, y) = (rng.range_usize(1..size - 1), rng.range_usize(1..size - 1));
+        if (x + y) % 2 == 1 {
+            maze[(x, y)] = Tile::Floor;
+        }
+    }
+
+    let (start, goal) = ((1, size - 2), (size - 2, 1));
+    let mut input = maze.map(|tile| match tile {
+        Tile::Wall => '#',
+        Tile::Floor => '.',
+    });
+    input[start] = 'S';
+    input[goal] = 'E';
+    input.render(|c| *c)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE_1: &str = r#"###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+"#;
+
+    #[test]
+    fn winning_score_works_for_example_1() {
+        // given
+        let (maze, start, goal) = parse(EXAMPLE_1).expect("expected example input to parse");
+
+        // when
+        let score = winning_score(&maze, start, goal);
+
+        // then
+        assert_eq!(score, Some((7036, 45)));
+    }
+
+    #[test]
+    fn path_frames_end_at_the_goal() {
+        // given
+        let (maze, start, goal) = parse(EXAMPLE_1).expect("expected example input to parse");
+
+        // when
+        let last = path_frames(&maze, start, goal).last();
+
+        // then
+        let last = last.expect("expected at least one frame");
+        assert_eq!(last.caption, "score 7036");
+        assert_eq!(
+            last.picture.get(goal).map(|cell| cell.bg),
+            Some(Some(Color::RED))
+        );
+    }
+}
@@ -0,0 +1,308 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{expect_prefix, parse_number, Answer, ParseError, Solution};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input<'a> = (Registers, Box<[u64]>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (registers, program) = input;
+        let output = run_program(program, *registers)?
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(output.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (registers, program) = input;
+        Ok(find_quine_input(program, *registers)?.into())
+    }
+
+    fn auxiliary_output(input: &Self::Input<'_>) -> Result<Option<String>, String> {
+        let (_, program) = input;
+        Ok(Some(format!("input program:\n{}", mnemonics(program)?)))
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(16)))
+    }
+}
+
+// This solution is specific to structures (division by eight) in my input and the example input
+// it may not work on all AoC inputs and will certainly not work on all valid programs
+pub fn find_quine_input(program: &[u64], registers: Registers) -> Result<u64, String> {
+    let mut a: u64 = 0;
+    for (ip, des_output) in program.iter().enumerate().rev() {
+        a = a
+            .checked_mul(8)
+            .ok_or_else(|| "program is too long to find a quine input".to_owned())?;
+        for i in 0..256 {
+            let Some(candidate) = a.checked_add(i) else {
+                break;
+            };
+            let output = run_program(
+                program,
+                Registers {
+                    a: candidate,
+                    ..registers
+                },
+            )?;
+            if output.first() == Some(des_output)
+                && (output.len() < 2 || program.get(ip + 1) == Some(&output[1]))
+            {
+                a |= i;
+                break;
+            }
+        }
+    }
+
+    let output = run_program(program, Registers { a, ..registers })?;
+    if output == program {
+        Ok(a)
+    } else {
+        Err(format!(
+            "output does not equal program, output is {output:?}"
+        ))
+    }
+}
+
+pub fn mnemonics(program: &[u64]) -> Result<String, String> {
+    let mut listing = String::with_capacity(program.len() * 8);
+    for (i, op) in program.chunks_exact(2).enumerate() {
+        let operator = op[0];
+        let operand = op[1];
+        let instruction = match operator {
+            0 => format!("adv {}", combo_operand_mnemonic(operand)?),
+            1 => format!("bxl {operand}"),
+            2 => format!("bst {}", combo_operand_mnemonic(operand)?),
+            3 => format!("jnz {operand}"),
+            4 => "bxc".to_owned(),
+            5 => format!("out {}", combo_operand_mnemonic(operand)?),
+            6 => format!("bdv {}", combo_operand_mnemonic(operand)?),
+            7 => format!("cdv {}", combo_operand_mnemonic(operand)?),
+            _ => return Err(format!("unknown operator '{operator}'")),
+        };
+        listing.push_str(&format!("{:02}\t{instruction}\n", i * 2));
+    }
+    Ok(listing)
+}
+
+fn combo_operand_mnemonic(op: u64) -> Result<String, String> {
+    match op {
+        0..=3 => Ok(op.to_string()),
+        4 => Ok("A".to_string()),
+        5 => Ok("B".to_string()),
+        6 => Ok("C".to_string()),
+        _ => Err(format!("unknown combo operand: '{op}'")),
+    }
+}
+
+// programs that run longer than this are assumed to loop forever
+const MAX_STEPS: usize = 1_000_000;
+
+pub fn run_program(program: &[u64], mut registers: Registers) -> Result<Vec<u64>, String> {
+    let mut output: Vec<u64> = Vec::with_capacity(16);
+    let mut ip: u64 = 0;
+    for _ in 0..MAX_STEPS {
+        if !handle_instruction(program, &mut ip, &mut registers, &mut output)? {
+            return Ok(output);
+        }
+    }
+    Err(format!("program did not halt within {MAX_STEPS} steps"))
+}
+
+fn handle_instruction(
+    program: &[u64],
+    ip: &mut u64,
+    registers: &mut Registers,
+    output: &mut Vec<u64>,
+) -> Result<bool, String> {
+    if ip.saturating_add(1) >= program.len() as u64 {
+        return Ok(false);
+    }
+    let operator = program[*ip as usize];
+    let operand = program[*ip as usize + 1];
+
+    match operator {
+        // adv
+        0 => {
+            registers.a = divide(registers.a, value_combo_operand(operand, registers)?);
+            *ip += 2;
+        }
+        // bxl
+        1 => {
+            registers.b ^= operand;
+            *ip += 2;
+        }
+        // bst
+        2 => {
+            registers.b = value_combo_operand(operand, registers)? % 8;
+            *ip += 2;
+        }
+        // jnz
+        3 => {
+            if registers.a != 0 {
+                *ip = operand;
+            } else {
+                *ip += 2;
+            }
+        }
+        // bxc
+        4 => {
+            registers.b ^= registers.c;
+            *ip += 2;
+        }
+        // out
+        5 => {
+            output.push(value_combo_operand(operand, registers)? % 8);
+            *ip += 2;
+        }
+        // bdv
+        6 => {
+            registers.b = divide(registers.a, value_combo_operand(operand, registers)?);
+            *ip += 2;
+        }
+        // cdv
+        7 => {
+            registers.c = divide(registers.a, value_combo_operand(operand, registers)?);
+            *ip += 2;
+        }
+        _ => {
+            return Err(format!("invalid opcode: {operator}"));
+        }
+    }
+
+    Ok(true)
+}
+
+// a / 2^exponent, without overflowing for large exponents
+fn divide(a: u64, exponent: u64) -> u64 {
+    a.checked_shr(exponent.try_into().unwrap_or(u32::MAX))
+        .unwrap_or(0)
+}
+
+fn value_combo_operand(operand: u64, registers: &Registers) -> Result<u64, String> {
+    match operand {
+        0..=3 => Ok(operand),
+        4 => Ok(registers.a),
+        5 => Ok(registers.b),
+        6 => Ok(registers.c),
+        _ => Err(format!("invalid operand: {operand}")),
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Registers {
+    a: u64,
+    b: u64,
+    c: u64,
+}
+
+pub fn parse(input: &str) -> Result<(Registers, Box<[u64]>), ParseError> {
+    let mut lines = input.lines();
+    let mut next_line = |expected: &str| {
+        lines
+            .next()
+            .ok_or_else(|| ParseError::after(input, expected))
+    };
+    let a: u64 = parse_number(expect_prefix(next_line("register A")?, "Register A: ")?)?;
+    let b: u64 = parse_number(expect_prefix(next_line("register B")?, "Register B: ")?)?;
+    let c: u64 = parse_number(expect_prefix(next_line("register C")?, "Register C: ")?)?;
+
+    let empty = next_line("an empty line")?;
+    if !empty.is_empty() {
+        return Err(ParseError::new(empty, "an empty line"));
+    }
+
+    let program: Box<[u64]> = expect_prefix(next_line("a program")?, "Program: ")?
+        .split(',')
+        .map(parse_number)
+        .collect::<Result<_, _>>()?;
+
+    Ok((Registers { a, b, c }, program))
+}
+
+// A program with the structure of the real inputs: one output per round, A is divided by 8 every
+// round and B and C are calculated from the lowest bits of A with a few XORs. The constants and
+// the order of the middle instructions are random, programs without a quine input are thrown
+// away. The size is ignored, all programs have 16 numbers.
+pub fn generate(rng: &mut Rng, _size: usize) -> String {
+    loop {
+        let mut middle = [[1, rng.range(0..8)], [4, rng.range(0..8)], [0, 3]];
+        rng.shuffle(&mut middle);
+        let program: Box<[u64]> = [[2, 4], [1, rng.range(0..8)], [7, 5]]
+            .into_iter()
+            .chain(middle)
+            .chain([[5, 5], [3, 0]])
+            .flatten()
+            .map(|n| n as u64)
+            .collect();
+        let registers = Registers {
+            a: rng.range(1 << 40..1 << 47) as u64,
+            b: 0,
+            c: 0,
+        };
+        if find_quine_input(&program, registers).is_ok() {
+            let program: Box<[String]> = program.iter().map(u64::to_string).collect();
+            return format!(
+                "Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+                registers.a,
+                program.join(",")
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE: &str = r#"Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+"#;
+
+    static QUINE_EXAMPLE: &str = r#"Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0
+"#;
+
+    #[test]
+    fn run_program_works_for_example() {
+        // given
+        let (registers, program) = parse(EXAMPLE).expect("expected example program to parse");
+
+        // when
+        let output = run_program(&program, registers);
+
+        // then
+        assert_eq!(output, Ok(vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]))
+    }
+
+    #[test]
+    fn run_quine_input_works_for_example() {
+        // given
+        let (registers, program) = parse(QUINE_EXAMPLE).expect("expected example program to parse");
+
+        // when
+        let a = find_quine_input(&program, registers);
+
+        // then
+        assert_eq!(a, Ok(117440));
+    }
+}
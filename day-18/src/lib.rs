@@ -0,0 +1,236 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{
+    expect_split, parse_number, Animation, Answer, Cell, Color, Frame, ParseError, Solution,
+};
+use grid::{Grid, Pos};
+use search::{bfs, Search};
+use std::fmt::Write;
+
+const WIDTH: u64 = 71;
+const FALLEN_BYTES: usize = 1024;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input<'a> = Box<[(u64, u64)]>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let bytes = parse(input)?;
+        if bytes.len() < FALLEN_BYTES {
+            return Err(ParseError::after(
+                input,
+                format!("at least {FALLEN_BYTES} bytes, found only {}", bytes.len()),
+            ));
+        }
+        Ok(bytes)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        shortest_path_after_bytes(&input[..FALLEN_BYTES], WIDTH)
+            .map(Answer::from)
+            .ok_or_else(|| "No path to bottom right corner.".to_owned())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        first_blocking_byte(input, WIDTH, FALLEN_BYTES)
+            .map(|(x, y)| Answer::Text(format!("{x},{y}")))
+            .ok_or_else(|| {
+                "even if all bytes fall, we can still reach the bottom left corner".to_owned()
+            })
+    }
+
+    fn animation<'a>(input: &'a Self::Input<'_>) -> Option<Animation<'a>> {
+        Some(Box::new(falling_frames(input, WIDTH)))
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(3450)))
+    }
+}
+
+pub fn first_blocking_byte(
+    bytes: &[(u64, u64)],
+    width: u64,
+    starting_len: usize,
+) -> Option<(u64, u64)> {
+    let mut lower = starting_len;
+    let mut upper = bytes.len();
+    while lower + 1 < upper {
+        let pivot = lower + (upper - lower) / 2;
+        if shortest_path_after_bytes(&bytes[..pivot], width).is_some() {
+            lower = pivot;
+        } else {
+            upper = pivot;
+        }
+    }
+
+    if upper == bytes.len() - 1 {
+        None
+    } else {
+        Some(bytes[lower])
+    }
+}
+
+pub fn shortest_path_after_bytes(bytes: &[(u64, u64)], width: u64) -> Option<u64> {
+    let mut corrupted: Grid<bool> = Grid::filled(width as usize, width as usize, false);
+    for byte in bytes {
+        corrupt(&mut corrupted, *byte);
+    }
+    escape(&corrupted).cost()
+}
+
+// The bytes falling one after the other, with a shortest path to the exit highlighted. Ends with
+// the first byte that blocks the way out.
+pub fn falling_frames(bytes: &[(u64, u64)], width: u64) -> impl Iterator<Item = Frame> + '_ {
+    let mut corrupted: Grid<bool> = Grid::filled(width as usize, width as usize, false);
+    let mut blocked = false;
+    bytes.iter().enumerate().map_while(move |(i, &(x, y))| {
+        if blocked {
+            return None;
+        }
+        corrupt(&mut corrupted, (x, y));
+        let mut picture = corrupted.picture(|byte| match byte {
+            true => Cell::new('#').fg(Color::RED),
+            false => Cell::new('.').fg(Color::DARK_GRAY),
+        });
+        let caption = match escape(&corrupted).path() {
+            Some(path) => {
+                picture.highlight(path.iter().copied(), Color::BLUE);
+                format!(
+                    "{} bytes fallen, {} steps to the exit",
+                    i + 1,
+                    path.len() - 1
+                )
+            }
+            None => {
+                blocked = true;
+                format!("{} bytes fallen, {x},{y} blocks the way to the exit", i + 1)
+            }
+        };
+        Some(Frame { picture, caption })
+    })
+}
+
+fn corrupt(corrupted: &mut Grid<bool>, (x, y): (u64, u64)) {
+    if let Some(byte) = corrupted.get_mut((x as usize, y as usize)) {
+        *byte = true;
+    }
+}
+
+fn escape(corrupted: &Grid<bool>) -> Search<Pos> {
+    let goal = (corrupted.width() - 1, corrupted.height() - 1);
+    bfs(
+        [(0, 0)],
+        |&pos: &Pos| corrupted.neighbours4(pos).filter(|next| !corrupted[*next]),
+        |pos| *pos == goal,
+    )
+}
+
+pub fn parse(input: &str) -> Result<Box<[(u64, u64)]>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (x, y) = expect_split(line, ",")?;
+            Ok((parse_number(x)?, parse_number(y)?))
+        })
+        .collect()
+}
+
+// Bytes falling onto distinct positions of the real memory space, never onto the start or the
+// exit. There are at least 2500 of them, enough to block the way out at some point. Byte lists
+// without a way out after the first 1024 bytes or that never block it are thrown away.
+pub fn generate(rng: &mut Rng, bytes: usize) -> String {
+    let bytes = bytes.clamp(2500, (WIDTH * WIDTH - 2) as usize);
+    let mut positions: Box<[(u64, u64)]> = (0..WIDTH)
+        .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+        .filter(|pos| *pos != (0, 0) && *pos != (WIDTH - 1, WIDTH - 1))
+        .collect();
+    loop {
+        rng.shuffle(&mut positions);
+        let fallen = &positions[..bytes];
+        if shortest_path_after_bytes(&fallen[..FALLEN_BYTES], WIDTH).is_some()
+            && first_blocking_byte(fallen, WIDTH, FALLEN_BYTES).is_some()
+        {
+            let mut input = String::with_capacity(bytes * 6);
+            for (x, y) in fallen {
+                let _ = writeln!(input, "{x},{y}");
+            }
+            return input;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE: &str = r#"5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+"#;
+
+    #[test]
+    fn shortest_path_after_bytes_works_for_example() {
+        // given
+        let bytes = parse(EXAMPLE).expect("expeced example input to parse");
+
+        // when
+        let dist = shortest_path_after_bytes(&bytes[..12], 7);
+
+        // then
+        assert_eq!(dist, Some(22));
+    }
+
+    #[test]
+    fn first_blocking_byte_works_for_example() {
+        // given
+        let bytes = parse(EXAMPLE).expect("expeced example input to parse");
+
+        // when
+        let i = first_blocking_byte(&bytes, 7, 13);
+
+        // then
+        assert_eq!(i, Some((6, 1)));
+    }
+
+    #[test]
+    fn falling_frames_stop_at_the_blocking_byte() {
+        // given
+        let bytes = parse(EXAMPLE).expect("expeced example input to parse");
+
+        // when
+        let last = falling_frames(&bytes, 7).last();
+
+        // then
+        assert_eq!(
+            last.map(|frame| frame.caption),
+            Some("21 bytes fallen, 6,1 blocks the way to the exit".to_owned())
+        );
+    }
+}
@@ -0,0 +1,182 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{Answer, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input<'a> = (HashSet<&'a str>, Box<[&'a str]>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (towels, designs) = input;
+        Ok(count_possible_designs(designs, towels).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (towels, designs) = input;
+        Ok(sum_design_options(designs, towels).into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(400)))
+    }
+}
+
+pub fn sum_design_options(designs: &[&str], towels: &HashSet<&str>) -> u64 {
+    let mut cache: HashMap<&str, u64> = HashMap::with_capacity(1024);
+    designs
+        .iter()
+        .map(|design| design_options(design, towels, &mut cache))
+        .sum()
+}
+
+fn design_options<'a>(
+    design: &'a str,
+    towels: &HashSet<&str>,
+    cache: &mut HashMap<&'a str, u64>,
+) -> u64 {
+    if let Some(n) = cache.get(design) {
+        return *n;
+    }
+    if design.is_empty() {
+        return 1;
+    }
+    let mut n = 0;
+    for subdesign in (1..=design.len()).filter_map(|i| design.get(i..)) {
+        if design
+            .strip_suffix(subdesign)
+            .map(|prefix| towels.contains(prefix))
+            .unwrap_or(false)
+        {
+            n += design_options(subdesign, towels, cache);
+        }
+    }
+    cache.insert(design, n);
+    n
+}
+
+pub fn count_possible_designs(designs: &[&str], towels: &HashSet<&str>) -> usize {
+    designs
+        .iter()
+        .filter(|design| design_possible(design, towels))
+        .count()
+}
+
+fn design_possible(design: &str, towels: &HashSet<&str>) -> bool {
+    let mut stack: Vec<&str> = Vec::with_capacity(design.len());
+    let mut seen: HashSet<&str> = HashSet::with_capacity(design.len());
+    stack.push(design);
+    while let Some(partial_design) = stack.pop() {
+        if partial_design.is_empty() {
+            return true;
+        }
+        for new_partial_design in towels
+            .iter()
+            .filter_map(|towel| partial_design.strip_prefix(towel))
+        {
+            if seen.contains(new_partial_design) {
+                continue;
+            }
+            seen.insert(new_partial_design);
+
+            stack.push(new_partial_design);
+        }
+    }
+    false
+}
+
+pub fn parse(input: &str) -> Result<(HashSet<&str>, Box<[&str]>), ParseError> {
+    // so many towels… are there any hitchhikers around?
+    let (towels, designs) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::after(input, "an empty line between towels and designs"))?;
+    let towels: HashSet<&str> = towels.split(", ").collect();
+    let designs: Box<[&str]> = designs.lines().collect();
+
+    Ok((towels, designs))
+}
+
+const COLORS: [char; 5] = ['w', 'u', 'b', 'r', 'g'];
+
+// About 450 distinct towels of 1 to 8 stripes and `designs` designs. Half of the designs are made
+// of towels, the others of random stripes. One colour has no towel of its own, so some of the
+// random designs are impossible.
+pub fn generate(rng: &mut Rng, designs: usize) -> String {
+    let missing = *rng.choose(&COLORS);
+    let mut towels: Vec<String> = Vec::with_capacity(450);
+    let mut seen: HashSet<String> = HashSet::with_capacity(450);
+    for _ in 0..450 {
+        let len = rng.range_usize(1..9);
+        let towel: String = (0..len).map(|_| *rng.choose(&COLORS)).collect();
+        if (towel.len() > 1 || !towel.starts_with(missing)) && seen.insert(towel.clone()) {
+            towels.push(towel);
+        }
+    }
+
+    let mut input = towels.join(", ");
+    input.push_str("\n\n");
+    for _ in 0..designs {
+        let len = rng.range_usize(20..61);
+        let mut design = String::with_capacity(len + 8);
+        if rng.chance(1, 2) {
+            while design.len() < len {
+                let towel: &String = rng.choose(&towels);
+                design.push_str(towel);
+            }
+        } else {
+            design.extend((0..len).map(|_| *rng.choose(&COLORS)));
+        }
+        input.push_str(&design);
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE: &str = r#"r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+"#;
+
+    #[test]
+    fn count_possible_designs_works_for_example() {
+        // given
+        let (towels, designs) = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let count = count_possible_designs(&designs, &towels);
+
+        // then
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn sum_design_options_works_for_example() {
+        // given
+        let (towels, designs) = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let sum = sum_design_options(&designs, &towels);
+
+        // then
+        assert_eq!(sum, 16);
+    }
+}
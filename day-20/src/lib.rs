@@ -0,0 +1,306 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{par_map, Answer, ParseError, Solution};
+use grid::{Grid, Pos};
+use search::{bfs, Cost};
+use std::collections::HashMap;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input<'a> = (Track, V2, V2);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (track, start, goal) = input;
+        Ok(count_good_cheats(track, *start, *goal, 2).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (track, start, goal) = input;
+        Ok(count_good_cheats(track, *start, *goal, 20).into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(141)))
+    }
+}
+
+// number of cheats that save at least 100 picoseconds
+pub fn count_good_cheats(track: &Track, start: V2, goal: V2, cheat_time: u64) -> usize {
+    find_path_with_cheat(track, start, goal, cheat_time)
+        .iter()
+        .filter(|(dist, _)| **dist >= 100)
+        .map(|(_, n)| *n)
+        .sum()
+}
+
+pub fn find_path_with_cheat(
+    track: &Track,
+    start: V2,
+    goal: V2,
+    cheat_time: u64,
+) -> HashMap<u64, usize> {
+    let uncheated_dist = find_path(track, start);
+    let uncheated_shortest_path = match uncheated_dist.get(&goal) {
+        Some(d) => d,
+        None => return HashMap::new(),
+    };
+    let uncheated_dist_inverse = find_path(track, goal);
+
+    let floor: Vec<(V2, Cost)> = uncheated_dist
+        .iter()
+        .filter(|(pos, _)| track[**pos] == Tile::Floor)
+        .map(|(pos, dist)| (*pos, *dist))
+        .collect();
+    // every tile on its own, the counts add up to the same in any order
+    let savings = par_map(&floor, |&((x, y), uncheated_from)| {
+        let mut savings: Vec<u64> = Vec::with_capacity(16);
+        for cheated_distance in 2..=cheat_time {
+            for dy in 0..=cheated_distance {
+                let dx = cheated_distance - dy;
+                for (cheat_x, cheat_y) in cheatable_tiles(track, x, y, dx as usize, dy as usize)
+                    .iter()
+                    .filter_map(|t| *t)
+                {
+                    if let Some(from_cheated) = uncheated_dist_inverse.get(&(cheat_x, cheat_y)) {
+                        let total_cheated_distance =
+                            uncheated_from + cheated_distance + from_cheated;
+                        if total_cheated_distance < *uncheated_shortest_path {
+                            savings.push(uncheated_shortest_path - total_cheated_distance);
+                        }
+                    }
+                }
+            }
+        }
+        savings
+    });
+
+    let mut cheated_dists: HashMap<u64, usize> = HashMap::with_capacity(1024);
+    for saving in savings.into_iter().flatten() {
+        *cheated_dists.entry(saving).or_insert(0) += 1;
+    }
+    cheated_dists
+}
+
+fn cheatable_tiles(track: &Track, x: usize, y: usize, dx: usize, dy: usize) -> [Option<V2>; 4] {
+    let mut result: [Option<V2>; 4] = [None; 4];
+    if dx < x {
+        if dy < y {
+            let cheat_x = x - dx;
+            let cheat_y = y - dy;
+            if track[(cheat_x, cheat_y)] == Tile::Floor {
+                result[0] = Some((cheat_x, cheat_y));
+            }
+        }
+        if y + dy < track.height() - 1 && dy != 0 {
+            let cheat_x = x - dx;
+            let cheat_y = y + dy;
+            if track[(cheat_x, cheat_y)] == Tile::Floor {
+                result[1] = Some((cheat_x, cheat_y));
+            }
+        }
+    }
+    if dx + x < track.width() - 1 && dx != 0 {
+        if dy < y {
+            let cheat_x = x + dx;
+            let cheat_y = y - dy;
+            if track[(cheat_x, cheat_y)] == Tile::Floor {
+                result[2] = Some((cheat_x, cheat_y));
+            }
+        }
+        if y + dy < track.height() - 1 && dy != 0 {
+            let cheat_x = x + dx;
+            let cheat_y = y + dy;
+            if track[(cheat_x, cheat_y)] == Tile::Floor {
+                result[3] = Some((cheat_x, cheat_y));
+            }
+        }
+    }
+    result
+}
+
+fn find_path(track: &Track, start: V2) -> HashMap<V2, Cost> {
+    bfs(
+        [start],
+        |&pos: &V2| {
+            track
+                .neighbours4(pos)
+                .filter(|next| track[*next] == Tile::Floor)
+        },
+        |_| false,
+    )
+    .into_distances()
+}
+
+pub type V2 = Pos;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Tile {
+    Wall,
+    Floor,
+}
+
+pub type Track = Grid<Tile>;
+
+pub fn parse(input: &str) -> Result<(Track, V2, V2), ParseError> {
+    let mut start: Option<V2> = None;
+    let mut goal: Option<V2> = None;
+    let track = Grid::parse(input, "'#', '.', 'S' or 'E'", |c, pos| match c {
+        '#' => Some(Tile::Wall),
+        '.' => Some(Tile::Floor),
+        'S' => {
+            start.get_or_insert(pos);
+            Some(Tile::Floor)
+        }
+        'E' => {
+            goal.get_or_insert(pos);
+            Some(Tile::Floor)
+        }
+        _ => None,
+    })?;
+    if let Some((pos, _)) = track
+        .iter()
+        .find(|(pos, tile)| track.is_border(*pos) && **tile != Tile::Wall)
+    {
+        return Err(grid::error_at(input, pos, "a wall ('#') around the track"));
+    }
+    let start = start.ok_or_else(|| ParseError::after(input, "a start ('S') in the track"))?;
+    let goal = goal.ok_or_else(|| ParseError::after(input, "an end ('E') in the track"))?;
+
+    Ok((track, start, goal))
+}
+
+// A single race track through a square (with an odd width of at least 5). The track is the
+// longest way from the top left corner through a maze carved by a random depth-first search,
+// everything else is wall, so there are lots of shortcuts through thin walls.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(5) | 1;
+    let mut maze: Track = Grid::filled(size, size, Tile::Wall);
+    let mut stack: Vec<V2> = vec![(1, 1)];
+    maze[(1, 1)] = Tile::Floor;
+    while let Some(&(x, y)) = stack.last() {
+        let next: Vec<V2> = [(0, -2), (2, 0), (0, 2), (-2, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy)| maze.offset((x, y), (dx, dy)))
+            .filter(|next| !maze.is_border(*next) && maze[*next] == Tile::Wall)
+            .collect();
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (nx, ny) = *rng.choose(&next);
+        maze[((x + nx) / 2, (y + ny) / 2)] = Tile::Floor;
+        maze[(nx, ny)] = Tile::Floor;
+        stack.push((nx, ny));
+    }
+
+    // the track ends at the tile furthest away from its start
+    let start = (1, 1);
+    let maze_walk = bfs(
+        [start],
+        |&pos: &V2| {
+            maze.neighbours4(pos)
+                .filter(|next| maze[*next] == Tile::Floor)
+        },
+        |_| false,
+    );
+    let Some((&goal, _)) = maze_walk
+        .distances()
+        .iter()
+        .max_by_key(|(pos, dist)| (**dist, **pos))
+    else {
+        unreachable!("the start is always part of the maze");
+    };
+    let way = maze_walk.path_to(&goal).unwrap_or_default();
+    let mut input = Grid::filled(size, size, '#');
+    for pos in way {
+        input[pos] = '.';
+    }
+    input[start] = 'S';
+    input[goal] = 'E';
+    input.render(|c| *c)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE: &str = r#"###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+"#;
+
+    #[test]
+    fn find_path_with_cheat_works_for_example() {
+        // given
+        let (track, start, goal) = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let shortcuts = find_path_with_cheat(&track, start, goal, 2);
+
+        // then
+        assert_eq!(shortcuts.len(), 11);
+        assert_eq!(shortcuts.get(&2), Some(&14));
+        assert_eq!(shortcuts.get(&4), Some(&14));
+        assert_eq!(shortcuts.get(&6), Some(&2));
+        assert_eq!(shortcuts.get(&8), Some(&4));
+        assert_eq!(shortcuts.get(&10), Some(&2));
+        assert_eq!(shortcuts.get(&12), Some(&3));
+        assert_eq!(shortcuts.get(&20), Some(&1));
+        assert_eq!(shortcuts.get(&36), Some(&1));
+        assert_eq!(shortcuts.get(&38), Some(&1));
+        assert_eq!(shortcuts.get(&40), Some(&1));
+        assert_eq!(shortcuts.get(&64), Some(&1));
+    }
+
+    #[test]
+    fn find_path_with_cheat_works_with_20_ps_for_example() {
+        // given
+        let (track, start, goal) = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let shortcuts = find_path_with_cheat(&track, start, goal, 20);
+
+        // then
+        let shortcuts_50: HashMap<u64, usize> = shortcuts
+            .iter()
+            .filter(|(dist, _)| **dist >= 50)
+            .map(|(d, n)| (*d, *n))
+            .collect();
+        println!("{shortcuts_50:?}");
+        assert_eq!(shortcuts_50.len(), 14);
+        assert_eq!(shortcuts_50.get(&50), Some(&32));
+        assert_eq!(shortcuts_50.get(&52), Some(&31));
+        assert_eq!(shortcuts_50.get(&54), Some(&29));
+        assert_eq!(shortcuts_50.get(&56), Some(&39));
+        assert_eq!(shortcuts_50.get(&58), Some(&25));
+        assert_eq!(shortcuts_50.get(&60), Some(&23));
+        assert_eq!(shortcuts_50.get(&62), Some(&20));
+        assert_eq!(shortcuts_50.get(&64), Some(&19));
+        assert_eq!(shortcuts_50.get(&66), Some(&12));
+        assert_eq!(shortcuts_50.get(&68), Some(&14));
+        assert_eq!(shortcuts_50.get(&70), Some(&12));
+        assert_eq!(shortcuts_50.get(&72), Some(&22));
+        assert_eq!(shortcuts_50.get(&74), Some(&4));
+        assert_eq!(shortcuts_50.get(&76), Some(&3));
+    }
+}
@@ -0,0 +1,153 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{par_map, parse_number, threads, Answer, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::ops::Range;
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input<'a> = Box<[u64]>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(sum_number_n(input, 2000).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(max_bananas(input, 2000).into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(2000)))
+    }
+}
+
+pub fn max_bananas(initial_numbers: &[u64], n: usize) -> i64 {
+    let prices: Vec<Box<[i64]>> = par_map(initial_numbers, |i| gen_prices(*i, n));
+    let price_changes: Vec<Box<[i64]>> = par_map(&prices, |price_list| {
+        price_list
+            .windows(2)
+            .map(|win| win[1] - win[0])
+            .collect::<Box<[i64]>>()
+    });
+
+    // Every worker sums up the prices of its share of the monkeys, only the first occurence of a
+    // sequence counts for each monkey. The sums below are the same in any order.
+    let share = price_changes.len().div_ceil(threads()).max(1);
+    let shares: Box<[Range<usize>]> = (0..price_changes.len())
+        .step_by(share)
+        .map(|start| start..(start + share).min(price_changes.len()))
+        .collect();
+    let totals: Vec<HashMap<&[i64], i64>> = par_map(&shares, |monkeys| {
+        let mut total_price_by_sequence: HashMap<&[i64], i64> = HashMap::with_capacity(2048);
+        let mut seen_sequences: HashSet<&[i64]> = HashSet::with_capacity(n);
+        for monkey_i in monkeys.clone() {
+            seen_sequences.clear();
+
+            for (i, sequence) in price_changes[monkey_i].windows(4).enumerate() {
+                if seen_sequences.insert(sequence) {
+                    *total_price_by_sequence.entry(sequence).or_insert(0) +=
+                        prices[monkey_i][i + 4];
+                }
+            }
+        }
+        total_price_by_sequence
+    });
+
+    let mut totals = totals.into_iter();
+    let mut total_price_by_sequence = totals.next().unwrap_or_default();
+    for total in totals {
+        for (sequence, price) in total {
+            *total_price_by_sequence.entry(sequence).or_insert(0) += price;
+        }
+    }
+    total_price_by_sequence.values().max().copied().unwrap_or(0)
+}
+
+fn gen_prices(initial_number: u64, n: usize) -> Box<[i64]> {
+    let mut prices = Vec::with_capacity(n + 1);
+    let mut secret = initial_number;
+    for _ in 0..n {
+        prices.push((secret % 10) as i64);
+        secret = next(secret);
+    }
+    prices.into_boxed_slice()
+}
+
+pub fn sum_number_n(initial_numbers: &[u64], n: u64) -> u64 {
+    par_map(initial_numbers, |i| number_n(*i, n))
+        .into_iter()
+        .sum()
+}
+
+fn number_n(mut prn: u64, n: u64) -> u64 {
+    for _ in 0..n {
+        prn = next(prn);
+    }
+    prn
+}
+
+const PRUNE_MOD: u64 = 16777216;
+
+fn next(n: u64) -> u64 {
+    let n = (n ^ (n * 64)) % PRUNE_MOD;
+    let n = (n ^ (n / 32)) % PRUNE_MOD;
+    (n ^ (n * 2048)) % PRUNE_MOD
+}
+
+pub fn parse(input: &str) -> Result<Box<[u64]>, ParseError> {
+    input.lines().map(parse_number).collect()
+}
+
+// Initial secret numbers, all of them below the pruning modulus.
+pub fn generate(rng: &mut Rng, buyers: usize) -> String {
+    let mut input = String::with_capacity(buyers * 9);
+    for _ in 0..buyers {
+        let _ = writeln!(input, "{}", rng.range(1..PRUNE_MOD as i64));
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn number_n_works_for_example() {
+        assert_eq!(number_n(123, 1), 15887950);
+        assert_eq!(number_n(123, 2), 16495136);
+        assert_eq!(number_n(123, 3), 527345);
+        assert_eq!(number_n(123, 4), 704524);
+        assert_eq!(number_n(123, 5), 1553684);
+        assert_eq!(number_n(123, 6), 12683156);
+        assert_eq!(number_n(123, 7), 11100544);
+        assert_eq!(number_n(123, 8), 12249484);
+        assert_eq!(number_n(123, 9), 7753432);
+        assert_eq!(number_n(123, 10), 5908254);
+
+        assert_eq!(number_n(1, 2000), 8685429);
+        assert_eq!(number_n(10, 2000), 4700978);
+        assert_eq!(number_n(100, 2000), 15273692);
+        assert_eq!(number_n(2024, 2000), 8667524);
+    }
+
+    #[test]
+    fn max_bananas_works_for_example() {
+        // given
+        let initial_secrets: &[u64] = &[1, 2, 3, 2024];
+
+        // when
+        let bananas = max_bananas(initial_secrets, 2000);
+
+        // then
+        assert_eq!(bananas, 23);
+    }
+}
@@ -0,0 +1,229 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{expect_split, Answer, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::iter::once;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input<'a> = Connections<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(count_chief_historian_candidates(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, String> {
+        Ok(password(input).into())
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(520)))
+    }
+}
+
+// I read this is NP-hard in general. Well, if it is an AoC problem, it can't be that bad I guess?
+pub fn password(connections: &Connections) -> String {
+    let mut maximum_clique: Vec<&str> = bron_kerbosh(
+        connections,
+        HashSet::new(),
+        connections.keys().copied().collect(),
+        HashSet::new(),
+        HashSet::new(),
+    )
+    .iter()
+    .copied()
+    .collect();
+    maximum_clique.sort_unstable();
+    maximum_clique.join(",")
+}
+
+fn bron_kerbosh<'a>(
+    connections: &Connections<'a>,
+    r: HashSet<&'a str>,
+    mut p: HashSet<&'a str>,
+    mut x: HashSet<&'a str>,
+    mut current_max_clique: HashSet<&'a str>,
+) -> HashSet<&'a str> {
+    if p.is_empty() && x.is_empty() && current_max_clique.len() < r.len() {
+        current_max_clique = r.clone();
+    }
+    while let Some(pc) = p.iter().next() {
+        let pc = *pc;
+        let r_with_pc: HashSet<&str> = r.iter().copied().chain(once(pc)).collect();
+        let empty = HashSet::new();
+        let pc_neighbours = connections.get(pc).unwrap_or(&empty);
+        let p_neighbours: HashSet<&str> = p.intersection(pc_neighbours).copied().collect();
+        let x_neighbours: HashSet<&str> = x.intersection(pc_neighbours).copied().collect();
+        current_max_clique = bron_kerbosh(
+            connections,
+            r_with_pc,
+            p_neighbours,
+            x_neighbours,
+            current_max_clique,
+        );
+        p.remove(&pc);
+        x.insert(pc);
+    }
+    current_max_clique
+}
+
+pub fn count_chief_historian_candidates(connections: &Connections) -> usize {
+    list_groups(connections)
+        .iter()
+        .filter(|group| group.iter().any(|pc| pc.starts_with('t')))
+        .count()
+}
+
+fn list_groups<'a>(connections: &Connections<'a>) -> HashSet<[&'a str; 3]> {
+    let mut groups: HashSet<[&str; 3]> = HashSet::with_capacity(connections.len() * 3);
+    for (a, others) in connections {
+        let others: Vec<&str> = others.iter().copied().collect();
+        for (i, b) in others.iter().enumerate() {
+            for c in &others[i + 1..] {
+                if let Some(b_conns) = connections.get(b) {
+                    if b_conns.contains(c) {
+                        let mut group: [&str; 3] = [a, b, c];
+                        group.sort_unstable();
+                        groups.insert(group);
+                    }
+                }
+            }
+        }
+    }
+    groups
+}
+
+pub type Connections<'a> = HashMap<&'a str, HashSet<&'a str>>;
+
+pub fn parse(input: &str) -> Result<Connections<'_>, ParseError> {
+    let mut connections: Connections = HashMap::with_capacity(input.len() / 6);
+    for line in input.lines() {
+        let (a, b) = expect_split(line, "-")?;
+        if a == b {
+            // would make the computer its own neighbour
+            return Err(ParseError::new(b, "a computer other than the first one"));
+        }
+        connections
+            .entry(a)
+            .or_insert(HashSet::with_capacity(16))
+            .insert(b);
+        connections
+            .entry(b)
+            .or_insert(HashSet::with_capacity(16))
+            .insert(a);
+    }
+    Ok(connections)
+}
+
+// the size of the LAN party in the real inputs
+const PARTY_SIZE: usize = 13;
+
+// A network of up to 676 computers with two letter names, each connected to about 12 random
+// others. A LAN party of 13 computers connected to each other is planted into it, random
+// connections hardly ever form a group that large.
+pub fn generate(rng: &mut Rng, computers: usize) -> String {
+    let mut names: Box<[String]> = (b'a'..=b'z')
+        .flat_map(|a| (b'a'..=b'z').map(move |b| format!("{}{}", a as char, b as char)))
+        .collect();
+    rng.shuffle(&mut names);
+    let names = &names[..computers.clamp(2, names.len())];
+
+    let mut connections: HashSet<(usize, usize)> = HashSet::with_capacity(names.len() * 8);
+    let party = PARTY_SIZE.min(names.len());
+    for a in 0..party {
+        for b in a + 1..party {
+            connections.insert((a, b));
+        }
+    }
+    for a in 0..names.len() {
+        for _ in 0..6 {
+            let b = rng.range_usize(0..names.len());
+            if a != b {
+                connections.insert((a.min(b), a.max(b)));
+            }
+        }
+    }
+
+    let mut connections: Vec<(usize, usize)> = connections.into_iter().collect();
+    // sorted first, because the order of a `HashSet` is different on every run
+    connections.sort_unstable();
+    rng.shuffle(&mut connections);
+    let mut input = String::with_capacity(connections.len() * 6);
+    for (a, b) in connections {
+        let (a, b) = if rng.chance(1, 2) { (a, b) } else { (b, a) };
+        let _ = writeln!(input, "{}-{}", names[a], names[b]);
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EXAMPLE: &str = r#"kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn
+"#;
+
+    #[test]
+    fn count_chief_historian_candidates_works_for_example() {
+        // given
+        let connections = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let count = count_chief_historian_candidates(&connections);
+
+        // then
+        assert_eq!(count, 7);
+    }
+
+    #[test]
+    fn password_works_for_example() {
+        // given
+        let connections = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let pw = password(&connections);
+
+        // then
+        assert_eq!(pw, "co,de,ka,ta");
+    }
+}
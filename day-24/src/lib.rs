@@ -0,0 +1,334 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{expect_split, Answer, ParseError, Solution};
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Input<'a> = (Inputs<'a>, Box<[Operation<'a>]>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (inputs, gates) = input;
+        Ok(calculate_output_number(gates, inputs.clone())?.into())
+    }
+
+    fn part2(_input: &Self::Input<'_>) -> Result<Answer, String> {
+        // screw this. It's christmas eve and I have no time. I'll just look at the graph and debug the
+        // wires manually
+        Ok(Answer::Unsolved)
+    }
+
+    fn auxiliary_output(input: &Self::Input<'_>) -> Result<Option<String>, String> {
+        let (_, gates) = input;
+        Ok(Some(graphviz_graph(gates)))
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(
+            rng,
+            options.size.unwrap_or(45),
+            options.swaps.unwrap_or(SWAPS),
+        ))
+    }
+}
+
+pub fn graphviz_graph(gates: &[Operation]) -> String {
+    let mut graph = String::with_capacity(gates.len() * 64);
+    graph.push_str("digraph {\n");
+
+    for (op_counter, (input1, input2, gate, output)) in gates.iter().enumerate() {
+        let op_name = match gate {
+            Gate::And => "AND",
+            Gate::Or => "OR",
+            Gate::Xor => "XOR",
+        };
+        let op_node_name = format!("{op_name}_{op_counter}");
+        graph.push_str(&format!("{op_node_name} [label = \"{op_name}\"];\n"));
+        graph.push_str(&format!("{input1} -> {op_node_name};\n"));
+        graph.push_str(&format!("{input2} -> {op_node_name};\n"));
+        graph.push_str(&format!("{op_node_name} -> {output};\n"));
+    }
+
+    graph.push('}');
+    graph
+}
+
+pub fn calculate_output_number(gates: &[Operation], input_values: Inputs) -> Result<u64, String> {
+    let values = resolve_gates(gates, input_values)?;
+    let mut z_values: Vec<(&str, bool)> = values
+        .iter()
+        .filter(|(name, _)| name.starts_with('z'))
+        .map(|(name, value)| (*name, *value))
+        .collect();
+    z_values.sort_unstable_by(|(name1, _), (name2, _)| name2.cmp(name1));
+    let mut number: u64 = 0;
+    for (_, digit) in z_values {
+        number <<= 1;
+        number |= if digit { 1 } else { 0 };
+    }
+    Ok(number)
+}
+
+fn resolve_gates<'a>(
+    gates: &[Operation<'a>],
+    mut values: HashMap<&'a str, bool>,
+) -> Result<HashMap<&'a str, bool>, String> {
+    // this is a bit inefficient, but should work fine for the input size
+    let mut gates: Vec<Operation> = gates.to_vec();
+    let mut queue: Vec<Operation> = Vec::with_capacity(gates.len());
+
+    while !gates.is_empty() {
+        for operation in &gates {
+            let (op1, op2, gate, target) = operation;
+            if let (Some(a), Some(b)) = (values.get(op1), values.get(op2)) {
+                values.insert(target, gate.apply(*a, *b));
+            } else {
+                queue.push(*operation);
+            }
+        }
+        if queue.len() == gates.len() {
+            let (op1, op2, _, target) = queue[0];
+            return Err(format!(
+                "unable to resolve '{target}', '{op1}' or '{op2}' has no value or depends on '{target}'"
+            ));
+        }
+        std::mem::swap(&mut gates, &mut queue);
+        queue.clear();
+    }
+    Ok(values)
+}
+
+pub fn parse(input: &str) -> Result<(Inputs<'_>, Box<[Operation<'_>]>), ParseError> {
+    let (inputs, gates) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::after(input, "an empty line between inputs and gates"))?;
+    let inputs = parse_inputs(inputs)?;
+    let gates = parse_gates(gates)?;
+    Ok((inputs, gates))
+}
+
+pub type Inputs<'a> = HashMap<&'a str, bool>;
+
+fn parse_inputs(input: &str) -> Result<Inputs<'_>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (name, value) = expect_split(line, ": ")?;
+            let value = match value {
+                "0" => false,
+                "1" => true,
+                _ => return Err(ParseError::new(value, "'0' or '1'")),
+            };
+            Ok((name, value))
+        })
+        .collect()
+}
+
+pub type Operation<'a> = (&'a str, &'a str, Gate, &'a str);
+
+fn parse_gates(input: &str) -> Result<Box<[Operation<'_>]>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (operation, target) = expect_split(line, " -> ")?;
+            let (operand_1, rest) = expect_split(operation, " ")?;
+            let (gate, operand_2) = expect_split(rest, " ")?;
+            let gate = match gate {
+                "AND" => Gate::And,
+                "OR" => Gate::Or,
+                "XOR" => Gate::Xor,
+                _ => return Err(ParseError::new(gate, "'AND', 'OR' or 'XOR'")),
+            };
+            Ok((operand_1, operand_2, gate, target))
+        })
+        .collect()
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Gate {
+    And,
+    Or,
+    Xor,
+}
+
+impl Gate {
+    fn apply(self, op1: bool, op2: bool) -> bool {
+        match self {
+            Gate::And => op1 && op2,
+            Gate::Or => op1 || op2,
+            Gate::Xor => op1 ^ op2,
+        }
+    }
+}
+
+// pairs of gates with swapped output wires, like in the real input
+const SWAPS: usize = 4;
+
+// A ripple-carry adder for two numbers of 2 to 63 bits (the sum has to fit into the `u64` of part
+// 1) with random values on the input wires. The outputs of `swaps` random pairs of gates are
+// swapped (at most as many pairs as there are gates), adders where that creates a loop are thrown
+// away.
+pub fn generate(rng: &mut Rng, bits: usize, swaps: usize) -> String {
+    let bits = bits.clamp(2, 63);
+    loop {
+        let mut names: HashSet<String> = HashSet::with_capacity(bits * 4);
+        let mut wire = || loop {
+            let name: String = (0..3)
+                .map(|_| char::from(b'a' + rng.below(23) as u8))
+                .collect();
+            if names.insert(name.clone()) {
+                return name;
+            }
+        };
+
+        let mut gates: Vec<(String, String, &str, String)> = Vec::with_capacity(bits * 5);
+        gates.push(("x00".to_owned(), "y00".to_owned(), "XOR", "z00".to_owned()));
+        let mut carry = wire();
+        gates.push(("x00".to_owned(), "y00".to_owned(), "AND", carry.clone()));
+        for i in 1..bits {
+            let (x, y) = (format!("x{i:02}"), format!("y{i:02}"));
+            let (sum, both, carried) = (wire(), wire(), wire());
+            let next_carry = if i + 1 == bits {
+                format!("z{bits:02}")
+            } else {
+                wire()
+            };
+            gates.push((x.clone(), y.clone(), "XOR", sum.clone()));
+            gates.push((x, y, "AND", both.clone()));
+            gates.push((sum.clone(), carry.clone(), "XOR", format!("z{i:02}")));
+            gates.push((sum, carry, "AND", carried.clone()));
+            gates.push((both, carried, "OR", next_carry.clone()));
+            carry = next_carry;
+        }
+
+        let mut swapped: Box<[usize]> = (0..gates.len()).collect();
+        rng.shuffle(&mut swapped);
+        let swaps = swaps.min(gates.len() / 2);
+        for pair in swapped[..swaps * 2].chunks(2) {
+            let output = gates[pair[0]].3.clone();
+            gates[pair[0]].3 = std::mem::replace(&mut gates[pair[1]].3, output);
+        }
+        rng.shuffle(&mut gates);
+
+        let mut input = String::with_capacity(bits * 100);
+        for name in ["x", "y"] {
+            for i in 0..bits {
+                let _ = writeln!(input, "{name}{i:02}: {}", rng.below(2));
+            }
+        }
+        input.push('\n');
+        for (a, b, gate, output) in gates {
+            let (a, b) = if rng.chance(1, 2) { (a, b) } else { (b, a) };
+            let _ = writeln!(input, "{a} {gate} {b} -> {output}");
+        }
+        let acyclic = parse(&input)
+            .is_ok_and(|(inputs, gates)| calculate_output_number(&gates, inputs).is_ok());
+        if acyclic {
+            return input;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::random::check_property;
+
+    static EXAMPLE: &str = r#"x00: 1
+x01: 0
+x02: 1
+x03: 1
+x04: 0
+y00: 1
+y01: 1
+y02: 1
+y03: 1
+y04: 1
+
+ntg XOR fgs -> mjb
+y02 OR x01 -> tnw
+kwq OR kpj -> z05
+x00 OR x03 -> fst
+tgd XOR rvg -> z01
+vdt OR tnw -> bfw
+bfw AND frj -> z10
+ffh OR nrd -> bqk
+y00 AND y03 -> djm
+y03 OR y00 -> psh
+bqk OR frj -> z08
+tnw OR fst -> frj
+gnj AND tgd -> z11
+bfw XOR mjb -> z00
+x03 OR x00 -> vdt
+gnj AND wpb -> z02
+x04 AND y00 -> kjc
+djm OR pbm -> qhw
+nrd AND vdt -> hwm
+kjc AND fst -> rvg
+y04 OR y02 -> fgs
+y01 AND x02 -> pbm
+ntg OR kjc -> kwq
+psh XOR fgs -> tgd
+qhw XOR tgd -> z09
+pbm OR djm -> kpj
+x03 XOR y03 -> ffh
+x00 XOR y04 -> ntg
+bfw OR bqk -> z06
+nrd XOR fgs -> wpb
+frj XOR qhw -> z04
+bqk OR frj -> z07
+y03 OR x01 -> nrd
+hwm AND bqk -> z03
+tgd XOR rvg -> z12
+tnw OR pbm -> gnj
+"#;
+
+    #[test]
+    fn calculate_output_number_works_for_example() {
+        // given
+        let (inputs, gates) = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let result = calculate_output_number(&gates, inputs);
+
+        // then
+        assert_eq!(result, Ok(2024));
+    }
+
+    #[test]
+    fn generate_without_swaps_adds_the_inputs() {
+        check_property(10, |rng| {
+            // given
+            let bits = rng.range_usize(2..20);
+            let input = generate(rng, bits, 0);
+            let (inputs, gates) = parse(&input).map_err(|e| e.to_string())?;
+            let number = |wire: char| {
+                inputs
+                    .iter()
+                    .filter(|(name, value)| name.starts_with(wire) && **value)
+                    .map(|(name, _)| 1 << name[1..].parse::<u64>().unwrap_or(0))
+                    .sum::<u64>()
+            };
+            let sum = number('x') + number('y');
+
+            // when
+            let output = calculate_output_number(&gates, inputs)?;
+
+            // then
+            if output != sum {
+                return Err(format!("expected {sum}, got {output}"));
+            }
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,223 @@
+#![forbid(unsafe_code)]
+
+use common::random::{GenOptions, Rng};
+use common::{Answer, ParseError, Solution};
+use std::collections::HashSet;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+
+    type Input<'a> = (Vec<u64>, Vec<u64>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, String> {
+        let (locks, keys) = input;
+        Ok(count_lock_key_pairs(locks, keys).into())
+    }
+
+    fn part2(_input: &Self::Input<'_>) -> Result<Answer, String> {
+        // there is no second puzzle on the last day
+        Ok(Answer::Unsolved)
+    }
+
+    fn generate(rng: &mut Rng, options: &GenOptions) -> Option<String> {
+        Some(generate(rng, options.size.unwrap_or(500)))
+    }
+}
+
+pub fn count_lock_key_pairs(locks: &[u64], keys: &[u64]) -> usize {
+    locks
+        .iter()
+        .map(|lock| keys.iter().filter(|key| *key & *lock == 0).count())
+        .sum()
+}
+
+pub fn parse(input: &str) -> Result<(Vec<u64>, Vec<u64>), ParseError> {
+    let mut locks: Vec<u64> = Vec::with_capacity(64);
+    let mut keys: Vec<u64> = Vec::with_capacity(64);
+
+    for block in input.split("\n\n") {
+        if block.starts_with('#') {
+            locks.push(parse_block(block)?);
+        } else {
+            keys.push(parse_block(block)?);
+        }
+    }
+    Ok((locks, keys))
+}
+
+fn parse_block(block: &str) -> Result<u64, ParseError> {
+    if block.len() > 42 {
+        // 7 rows × (5 columns + line breaks), at most blocks the last linebreak
+        // split away
+        return Err(ParseError::new(
+            block,
+            "a block of 7 lines with 5 characters",
+        ));
+    }
+    block
+        .char_indices()
+        .filter(|(_, c)| *c != '\n')
+        .try_fold(0u64, |value, (i, c)| match c {
+            '#' => Ok((value << 1) | 1),
+            '.' => Ok(value << 1),
+            _ => Err(ParseError::at_char(block, i, "'#' or '.'")),
+        })
+}
+
+// the inverse of `parse_block`, 7 lines of 5 characters without the final line break
+pub fn render_block(block: u64) -> String {
+    let mut out = String::with_capacity(42);
+    for i in (0..35).rev() {
+        out.push(if block & (1 << i) != 0 { '#' } else { '.' });
+        if i % 5 == 0 && i > 0 {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+// the number of different locks (and keys) with pin heights from 0 to 5
+const MAX_SCHEMATICS: usize = 2 * 6usize.pow(5);
+
+// Distinct schematics of locks and keys (about half of each), at most `MAX_SCHEMATICS`.
+pub fn generate(rng: &mut Rng, schematics: usize) -> String {
+    let schematics = schematics.min(MAX_SCHEMATICS);
+    let mut blocks: Vec<u64> = Vec::with_capacity(schematics);
+    let mut seen: HashSet<u64> = HashSet::with_capacity(schematics);
+    while blocks.len() < schematics {
+        let block = random_schematic(rng);
+        if seen.insert(block) {
+            blocks.push(block);
+        }
+    }
+    let blocks: Box<[String]> = blocks.into_iter().map(render_block).collect();
+    blocks.join("\n\n") + "\n"
+}
+
+fn random_schematic(rng: &mut Rng) -> u64 {
+    let lock = rng.chance(1, 2);
+    let mut block = 0u64;
+    for x in 0..5 {
+        let height = rng.range(0..6);
+        for y in 0..7 {
+            let filled = if lock { y <= height } else { 6 - y <= height };
+            if filled {
+                block |= 1 << (34 - (5 * y + x));
+            }
+        }
+    }
+    block
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::random::check_property;
+
+    static EXAMPLE: &str = r#"#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####
+"#;
+
+    #[test]
+    fn count_lock_key_pairs_works_for_example() {
+        // given
+        let (locks, keys) = parse(EXAMPLE).expect("expected example input to parse");
+
+        // when
+        let fits = count_lock_key_pairs(&locks, &keys);
+
+        // then
+        assert_eq!(fits, 3);
+    }
+
+    // a lock (filled from the top) or key (filled from the bottom) with random pin heights
+    #[test]
+    fn parse_block_round_trips_through_render_block() {
+        check_property(500, |rng| {
+            // given
+            let block = rng.next_u64() & ((1 << 35) - 1);
+
+            // when
+            let parsed = parse_block(&render_block(block));
+
+            // then
+            match parsed {
+                Ok(parsed) if parsed == block => Ok(()),
+                other => Err(format!("{block:035b} parsed to {other:?}")),
+            }
+        });
+    }
+
+    #[test]
+    fn parse_sorts_rendered_schematics_into_locks_and_keys() {
+        check_property(200, |rng| {
+            // given
+            let schematics: Vec<u64> = (0..rng.range_usize(1..20))
+                .map(|_| random_schematic(rng))
+                .collect();
+            let input: Vec<String> = schematics.iter().map(|s| render_block(*s)).collect();
+
+            // when
+            let parsed = parse(&(input.join("\n\n") + "\n"));
+
+            // then
+            let locks: Vec<u64> = schematics
+                .iter()
+                .copied()
+                .filter(|s| s & (1 << 34) != 0)
+                .collect();
+            let keys: Vec<u64> = schematics
+                .iter()
+                .copied()
+                .filter(|s| s & (1 << 34) == 0)
+                .collect();
+            match parsed {
+                Ok(parsed) if parsed == (locks.clone(), keys.clone()) => Ok(()),
+                other => Err(format!("expected {:?}, parsed {other:?}", (locks, keys))),
+            }
+        });
+    }
+}
@@ -0,0 +1,367 @@
+#![forbid(unsafe_code)]
+
+use common::{Cell, ParseError, Picture};
+use std::fmt;
+use std::iter::successors;
+use std::ops::{Index, IndexMut};
+
+// x counts the columns from the left, y the rows from the top
+pub type Pos = (usize, usize);
+pub type Offset = (isize, isize);
+
+// clockwise, starting upwards
+pub const NEIGHBOURS_4: [Offset; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+pub const NEIGHBOURS_8: [Offset; 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+// A rectangular grid of tiles, stored row by row.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Box<[T]>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: impl Into<Box<[T]>>) -> Self {
+        let cells = cells.into();
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "number of cells does not match a {width}×{height} grid"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..width * height)
+            .map(|i| cell((i % width, i / width)))
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    // Parses a map with one character per tile. `tile` maps a character (and its position) to a
+    // tile, characters it returns `None` for are reported as error, together with `expected`.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut tile: impl FnMut(char, Pos) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let width = input
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::after(input, "at least one line"))?
+            .chars()
+            .count();
+        let height = input.lines().count();
+        let mut cells: Vec<T> = Vec::with_capacity(width * height);
+        for (y, line) in input.lines().enumerate() {
+            if line.chars().count() != width {
+                return Err(ParseError::new(line, format!("a line of length {width}")));
+            }
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                let cell = tile(c, (x, y)).ok_or_else(|| ParseError::at_char(line, i, expected))?;
+                cells.push(cell);
+            }
+        }
+        Ok(Grid::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    // the outermost rows and columns
+    pub fn is_border(&self, (x, y): Pos) -> bool {
+        self.contains((x, y)) && (x == 0 || y == 0 || x + 1 == self.width || y + 1 == self.height)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.0 + pos.1 * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 + pos.1 * self.width])
+        } else {
+            None
+        }
+    }
+
+    // the position for signed coordinates, if they are inside the grid
+    pub fn checked_pos(&self, (x, y): Offset) -> Option<Pos> {
+        let pos = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        self.contains(pos).then_some(pos)
+    }
+
+    pub fn get_signed(&self, pos: Offset) -> Option<&T> {
+        self.get(self.checked_pos(pos)?)
+    }
+
+    pub fn get_signed_mut(&mut self, pos: Offset) -> Option<&mut T> {
+        let pos = self.checked_pos(pos)?;
+        self.get_mut(pos)
+    }
+
+    // one step from `pos` in direction `dir`, if that is still inside the grid
+    pub fn offset(&self, (x, y): Pos, (dx, dy): Offset) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_4
+            .iter()
+            .filter_map(move |dir| self.offset(pos, *dir))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_8
+            .iter()
+            .filter_map(move |dir| self.offset(pos, *dir))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    // all tiles from `start` (inclusive) in direction `dir` until the edge of the grid
+    pub fn ray(&self, start: Pos, dir: Offset) -> impl Iterator<Item = &T> {
+        successors(self.contains(start).then_some(start), move |pos| {
+            self.offset(*pos, dir)
+        })
+        .map(|pos| &self[pos])
+    }
+
+    // from `start` down to the right
+    pub fn diagonal(&self, start: Pos) -> impl Iterator<Item = &T> {
+        self.ray(start, (1, 1))
+    }
+
+    // from `start` down to the left
+    pub fn anti_diagonal(&self, start: Pos) -> impl Iterator<Item = &T> {
+        self.ray(start, (-1, 1))
+    }
+
+    pub fn swap(&mut self, a: Pos, b: Pos) {
+        assert!(self.contains(a) && self.contains(b), "swap outside of grid");
+        self.cells
+            .swap(a.0 + a.1 * self.width, b.0 + b.1 * self.width);
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // one character per tile and one line per row, the inverse of `parse`
+    pub fn render(&self, mut tile: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            out.extend(row.iter().map(&mut tile));
+            out.push('\n');
+        }
+        out
+    }
+
+    // like `render`, but with colours, so overlays can be drawn on top of it
+    pub fn picture(&self, mut tile: impl FnMut(&T) -> Cell) -> Picture {
+        Picture::from_fn(self.width, self.height, |pos| tile(&self[pos]))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is outside of the grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// An error pointing at the character of tile `pos` in the map the grid was parsed from, for
+// checks that can only be done on the whole grid (e.g. a wall around the map).
+pub fn error_at(input: &str, (x, y): Pos, expected: impl Into<String>) -> ParseError {
+    match input.lines().nth(y) {
+        Some(line) => match line.char_indices().nth(x) {
+            Some((i, _)) => ParseError::at_char(line, i, expected),
+            None => ParseError::after(line, expected),
+        },
+        None => ParseError::after(input, expected),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MAP: &str = "#####\n#S.E#\n#####\n";
+
+    #[test]
+    fn parse_maps_tiles() {
+        // given
+        let mut start: Option<Pos> = None;
+
+        // when
+        let grid = Grid::parse(MAP, "'#', '.', 'S' or 'E'", |c, pos| match c {
+            '#' => Some(false),
+            '.' | 'E' => Some(true),
+            'S' => {
+                start = Some(pos);
+                Some(true)
+            }
+            _ => None,
+        });
+
+        // then
+        let grid = grid.expect("expected map to parse");
+        assert_eq!((grid.width(), grid.height()), (5, 3));
+        assert_eq!(start, Some((1, 1)));
+        assert_eq!(
+            grid.render(|open| if *open { '.' } else { '#' }),
+            "#####\n#...#\n#####\n"
+        );
+    }
+
+    #[test]
+    fn parse_reports_unknown_tiles_and_uneven_lines() {
+        // when
+        let unknown = Grid::parse("..\n.x\n", "'.'", |c, _| (c == '.').then_some(()))
+            .expect_err("expected unknown tile to be rejected")
+            .locate("..\n.x\n");
+        let uneven =
+            Grid::parse("..\n...\n", "'.'", |_, _| Some(())).expect_err("expected uneven line");
+
+        // then
+        let location = unknown.location.expect("expected error to have a location");
+        assert_eq!((location.line, location.column), (2, 2));
+        assert_eq!(uneven.snippet, "...");
+    }
+
+    #[test]
+    fn neighbours_stay_inside_the_grid() {
+        // given
+        let grid = Grid::filled(3, 2, 0);
+
+        // when
+        let corner4: Vec<Pos> = grid.neighbours4((0, 0)).collect();
+        let corner8: Vec<Pos> = grid.neighbours8((2, 1)).collect();
+
+        // then
+        assert_eq!(corner4, vec![(1, 0), (0, 1)]);
+        assert_eq!(corner8, vec![(2, 0), (1, 1), (1, 0)]);
+    }
+
+    #[test]
+    fn views_and_signed_access_work() {
+        // given
+        let grid = Grid::from_fn(3, 3, |(x, y)| x + 3 * y);
+
+        // then
+        assert_eq!(grid.row(1), &[3, 4, 5]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![2, 5, 8]);
+        assert_eq!(
+            grid.diagonal((0, 0)).copied().collect::<Vec<_>>(),
+            vec![0, 4, 8]
+        );
+        assert_eq!(
+            grid.anti_diagonal((2, 0)).copied().collect::<Vec<_>>(),
+            vec![2, 4, 6]
+        );
+        assert_eq!(
+            grid.ray((1, 2), (0, -1)).copied().collect::<Vec<_>>(),
+            vec![7, 4, 1]
+        );
+        assert_eq!(grid.get_signed((-1, 0)), None);
+        assert_eq!(grid.get_signed((1, 1)), Some(&4));
+        assert_eq!(grid.to_string(), "012\n345\n678\n");
+    }
+}
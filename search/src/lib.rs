@@ -0,0 +1,417 @@
+#![forbid(unsafe_code)]
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+pub type Cost = u64;
+
+// The result of a search: the distances from the start states, the predecessor DAG of all optimal
+// paths, and the goal states that were reached with the lowest cost.
+//
+// Searches stop as soon as every state that is as cheap as the cheapest goal is done, so
+// distances of states beyond that may only be upper bounds. If no state is a goal, the whole
+// reachable state space is searched and all distances are exact.
+#[derive(Clone, Debug)]
+pub struct Search<S> {
+    distances: HashMap<S, Cost>,
+    predecessors: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    pub fn distance(&self, state: &S) -> Option<Cost> {
+        self.distances.get(state).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<S, Cost> {
+        &self.distances
+    }
+
+    pub fn into_distances(self) -> HashMap<S, Cost> {
+        self.distances
+    }
+
+    // all goal states reached with the lowest cost, in the order they were found
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    pub fn goal(&self) -> Option<&S> {
+        self.goals.first()
+    }
+
+    // cost of the cheapest goal
+    pub fn cost(&self) -> Option<Cost> {
+        self.goal().and_then(|goal| self.distance(goal))
+    }
+
+    // the states that come right before `state` on an optimal path (empty for the start states)
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors
+            .get(state)
+            .map(|preds| preds.as_slice())
+            .unwrap_or(&[])
+    }
+
+    // one optimal path from a start state to `target`, both inclusive
+    pub fn path_to(&self, target: &S) -> Option<Vec<S>> {
+        self.distances.get(target)?;
+        let mut path = vec![target.clone()];
+        while let Some(pred) = self.predecessors(path.last().unwrap()).first() {
+            path.push(pred.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // one optimal path to the first goal
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal()?)
+    }
+
+    // every optimal path to `target`. There can be exponentially many of them, use `count_paths_to`
+    // or `states_on_paths_to` if the paths themselves are not needed.
+    pub fn all_paths_to(&self, target: &S) -> Vec<Vec<S>> {
+        if !self.distances.contains_key(target) {
+            return Vec::new();
+        }
+        let preds = self.predecessors(target);
+        if preds.is_empty() {
+            return vec![vec![target.clone()]];
+        }
+        preds
+            .iter()
+            .flat_map(|pred| self.all_paths_to(pred))
+            .map(|mut path| {
+                path.push(target.clone());
+                path
+            })
+            .collect()
+    }
+
+    pub fn count_paths_to(&self, target: &S) -> u64 {
+        let mut counts: HashMap<S, u64> = HashMap::with_capacity(self.distances.len());
+        self.count_paths_cached(target, &mut counts)
+    }
+
+    fn count_paths_cached(&self, state: &S, counts: &mut HashMap<S, u64>) -> u64 {
+        if !self.distances.contains_key(state) {
+            return 0;
+        }
+        if let Some(count) = counts.get(state) {
+            return *count;
+        }
+        let preds = self.predecessors(state);
+        let count = if preds.is_empty() {
+            1
+        } else {
+            preds
+                .iter()
+                .map(|pred| self.count_paths_cached(pred, counts))
+                .sum()
+        };
+        counts.insert(state.clone(), count);
+        count
+    }
+
+    // all states that are part of any optimal path to one of the targets
+    pub fn states_on_paths_to(&self, targets: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut states: HashSet<S> = HashSet::with_capacity(self.distances.len());
+        let mut stack: Vec<S> = targets
+            .into_iter()
+            .filter(|target| self.distances.contains_key(target))
+            .collect();
+        while let Some(state) = stack.pop() {
+            if states.contains(&state) {
+                continue;
+            }
+            stack.extend(self.predecessors(&state).iter().cloned());
+            states.insert(state);
+        }
+        states
+    }
+}
+
+// Breadth first search, for state spaces where every step costs the same.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances: HashMap<S, Cost> = HashMap::with_capacity(1024);
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::with_capacity(1024);
+    let mut goals: Vec<S> = Vec::with_capacity(4);
+    let mut queue: VecDeque<S> = VecDeque::with_capacity(1024);
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    let mut goal_cost: Option<Cost> = None;
+    while let Some(state) = queue.pop_front() {
+        let dist = distances[&state];
+        if goal_cost.is_some_and(|cost| dist > cost) {
+            break;
+        }
+        if is_goal(&state) {
+            goal_cost.get_or_insert(dist);
+            goals.push(state);
+            continue;
+        }
+        for next in successors(&state) {
+            match distances.get(&next) {
+                None => {
+                    distances.insert(next.clone(), dist + 1);
+                    predecessors.insert(next.clone(), vec![state.clone()]);
+                    queue.push_back(next);
+                }
+                Some(d) if *d == dist + 1 => {
+                    predecessors.entry(next).or_default().push(state.clone());
+                }
+                Some(_) => (),
+            }
+        }
+    }
+
+    Search {
+        distances,
+        predecessors,
+        goals,
+    }
+}
+
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, Cost)>,
+{
+    astar(starts, successors, |_| 0, is_goal)
+}
+
+// A* search. The heuristic has to be consistent (never overestimate the remaining cost, not even
+// after a step), otherwise the result is not guaranteed to be optimal.
+pub fn astar<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> Cost,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, Cost)>,
+{
+    let mut distances: HashMap<S, Cost> = HashMap::with_capacity(1024);
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::with_capacity(1024);
+    let mut goals: Vec<S> = Vec::with_capacity(4);
+    let mut done: HashSet<S> = HashSet::with_capacity(1024);
+    // the heap only holds indices into `queued`, so states do not need to be `Ord`
+    let mut queued: Vec<S> = Vec::with_capacity(1024);
+    let mut heap: BinaryHeap<Reverse<(Cost, Cost, usize)>> = BinaryHeap::with_capacity(1024);
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            heap.push(Reverse((heuristic(&start), 0, queued.len())));
+            queued.push(start);
+        }
+    }
+
+    let mut goal_cost: Option<Cost> = None;
+    while let Some(Reverse((estimate, dist, i))) = heap.pop() {
+        if goal_cost.is_some_and(|cost| estimate > cost) {
+            break;
+        }
+        let state = &queued[i];
+        if distances[state] < dist || done.contains(state) {
+            continue;
+        }
+        let state = state.clone();
+        done.insert(state.clone());
+        if is_goal(&state) {
+            goal_cost.get_or_insert(dist);
+            goals.push(state);
+            continue;
+        }
+        for (next, cost) in successors(&state) {
+            let next_dist = dist + cost;
+            match distances.get(&next) {
+                Some(d) if *d < next_dist => (),
+                Some(d) if *d == next_dist => {
+                    predecessors.entry(next).or_default().push(state.clone());
+                }
+                _ => {
+                    distances.insert(next.clone(), next_dist);
+                    predecessors.insert(next.clone(), vec![state.clone()]);
+                    heap.push(Reverse((
+                        next_dist + heuristic(&next),
+                        next_dist,
+                        queued.len(),
+                    )));
+                    queued.push(next);
+                }
+            }
+        }
+    }
+
+    Search {
+        distances,
+        predecessors,
+        goals,
+    }
+}
+
+// The `k` cheapest paths to any goal, cheapest first. Paths may visit a state more than once, so
+// this only makes sense for state spaces where that is wanted or impossible (e.g. a DAG).
+pub fn k_shortest_paths<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+    k: usize,
+) -> Vec<(Vec<S>, Cost)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, Cost)>,
+{
+    // every entry is a path, stored as its last state and the index of the path leading there
+    let mut nodes: Vec<(S, Option<usize>)> = Vec::with_capacity(1024);
+    let mut heap: BinaryHeap<Reverse<(Cost, usize)>> = BinaryHeap::with_capacity(1024);
+    let mut visits: HashMap<S, usize> = HashMap::with_capacity(1024);
+    for start in starts {
+        heap.push(Reverse((0, nodes.len())));
+        nodes.push((start, None));
+    }
+
+    let mut paths: Vec<(Vec<S>, Cost)> = Vec::with_capacity(k);
+    while let Some(Reverse((cost, i))) = heap.pop() {
+        if paths.len() == k {
+            break;
+        }
+        let state = nodes[i].0.clone();
+        let count = visits.entry(state.clone()).or_insert(0);
+        // a state on one of the k shortest paths is never visited by more than k of them
+        if *count >= k {
+            continue;
+        }
+        *count += 1;
+        if is_goal(&state) {
+            let mut path: Vec<S> = Vec::with_capacity(16);
+            let mut node = Some(i);
+            while let Some(n) = node {
+                path.push(nodes[n].0.clone());
+                node = nodes[n].1;
+            }
+            path.reverse();
+            paths.push((path, cost));
+            continue;
+        }
+        for (next, step) in successors(&state) {
+            heap.push(Reverse((cost + step, nodes.len())));
+            nodes.push((next, Some(i)));
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // a 4×4 grid without obstacles, moving right or down only
+    fn right_or_down(&(x, y): &(u8, u8)) -> Vec<(u8, u8)> {
+        let mut next = Vec::with_capacity(2);
+        if x < 3 {
+            next.push((x + 1, y));
+        }
+        if y < 3 {
+            next.push((x, y + 1));
+        }
+        next
+    }
+
+    #[test]
+    fn bfs_finds_all_optimal_paths() {
+        // when
+        let search = bfs([(0, 0)], right_or_down, |pos| *pos == (2, 2));
+
+        // then
+        assert_eq!(search.cost(), Some(4));
+        assert_eq!(search.count_paths_to(&(2, 2)), 6);
+        assert_eq!(search.all_paths_to(&(2, 2)).len(), 6);
+        assert_eq!(search.states_on_paths_to([(2, 2)]).len(), 9);
+        let path = search.path().expect("expected a path");
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(2, 2)));
+        assert_eq!(path.len(), 5);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_agree() {
+        // given
+        // turning is expensive, like in a reindeer maze
+        let successors = |&(pos, dir): &((i32, i32), u8)| {
+            let step = match dir {
+                0 => (pos.0 + 1, pos.1),
+                _ => (pos.0, pos.1 + 1),
+            };
+            let mut next = vec![((pos, 1 - dir), 1000)];
+            if step.0 <= 5 && step.1 <= 5 {
+                next.push(((step, dir), 1));
+            }
+            next
+        };
+        let is_goal = |(pos, _): &((i32, i32), u8)| *pos == (5, 5);
+
+        // when
+        let plain = dijkstra([((0, 0), 0)], successors, is_goal);
+        let guided = astar(
+            [((0, 0), 0)],
+            successors,
+            |((x, y), _)| (10 - x - y) as Cost,
+            is_goal,
+        );
+
+        // then
+        assert_eq!(plain.cost(), Some(1010));
+        assert_eq!(guided.cost(), Some(1010));
+        // right to the edge, one turn and down to the goal
+        assert_eq!(plain.states_on_paths_to(plain.goals().to_vec()).len(), 12);
+    }
+
+    #[test]
+    fn k_shortest_paths_returns_paths_in_order() {
+        // given
+        let edges: HashMap<char, Vec<(char, Cost)>> = HashMap::from([
+            ('a', vec![('b', 1), ('c', 2)]),
+            ('b', vec![('d', 1), ('c', 0)]),
+            ('c', vec![('d', 3)]),
+        ]);
+
+        // when
+        let paths = k_shortest_paths(
+            ['a'],
+            |s| edges.get(s).cloned().unwrap_or_default(),
+            |s| *s == 'd',
+            3,
+        );
+
+        // then
+        assert_eq!(
+            paths,
+            vec![
+                (vec!['a', 'b', 'd'], 2),
+                (vec!['a', 'b', 'c', 'd'], 4),
+                (vec!['a', 'c', 'd'], 5),
+            ]
+        );
+    }
+}